@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::error::AocError;
+use crate::solver::Answer;
+use crate::string_functions::split_and_clean;
+
+/// Expected answers, stored as one `day.part = value` line per part.
+///
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    values: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        return Answers::default();
+    }
+
+    pub fn parse(input: &str) -> Result<Answers, AocError> {
+        let mut answers = Answers::new();
+        for line in input.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (day, part, value) = Answers::parse_line(trimmed).map_err(|e| e.locate(input))?;
+            if answers
+                .values
+                .insert((day, part), value.to_string())
+                .is_some()
+            {
+                return Err(AocError::parse("duplicate answer", trimmed).locate(input));
+            }
+        }
+
+        return Ok(answers);
+    }
+
+    fn parse_line(input: &str) -> Result<(u32, u32, &str), AocError> {
+        let parts = split_and_clean(input, "=");
+        if parts.len() != 2 {
+            return Err(AocError::parse("expected 'day.part = value'", input));
+        }
+
+        let key = split_and_clean(parts[0], ".");
+        if key.len() != 2 {
+            return Err(AocError::parse("expected 'day.part'", parts[0]));
+        }
+
+        let day = key[0]
+            .parse::<u32>()
+            .map_err(|_| AocError::parse("the day is not a number", key[0]))?;
+        let part = key[1]
+            .parse::<u32>()
+            .map_err(|_| AocError::parse("the part is not a number", key[1]))?;
+
+        return Ok((day, part, parts[1]));
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        return self.values.get(&(day, part)).map(|v| v.as_str());
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: &Answer) {
+        self.values.insert((day, part), answer.to_string());
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), value) in &self.values {
+            writeln!(f, "{day}.{part} = {value}")?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Answers;
+    use crate::error::AocError;
+    use crate::solver::Answer;
+
+    #[test]
+    fn test_parse() {
+        let input = "\
+# answers for the inputs in the repository root
+7.1 = 249515436
+
+9.2=-3
+";
+        let actual = Answers::parse(input).unwrap();
+
+        assert_eq!(actual.get(7, 1), Some("249515436"));
+        assert_eq!(actual.get(9, 2), Some("-3"));
+        assert_eq!(actual.get(7, 2), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_line() {
+        let input = "7.1 = 249515436\nseven.2 = 1";
+        let actual = Answers::parse(input);
+
+        match actual {
+            Err(AocError::Parse(error)) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.column, Some(1));
+                assert_eq!(error.snippet, "seven");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_duplicate() {
+        assert!(Answers::parse("1.1 = 2\n1.1 = 3").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::new();
+        answers.insert(10, 2, &Answer::Unsigned(325));
+        answers.insert(9, 2, &Answer::Signed(1077));
+        answers.insert(9, 1, &Answer::Signed(18));
+
+        let text = answers.to_string();
+
+        assert_eq!(text, "9.1 = 18\n9.2 = 1077\n10.2 = 325\n");
+        assert_eq!(Answers::parse(&text).unwrap(), answers);
+    }
+}
@@ -0,0 +1,207 @@
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::json;
+use crate::registry::Day;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+pub struct Measurement {
+    pub stage: &'static str,
+    pub statistics: Result<Statistics, AocError>,
+}
+
+impl Statistics {
+    /// Summarizes the given samples, which must not be empty.
+    pub fn from_samples(samples: &[Duration]) -> Statistics {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        let total: Duration = sorted.iter().sum();
+
+        return Statistics {
+            min: sorted[0],
+            median,
+            mean: total / sorted.len() as u32,
+            max: sorted[sorted.len() - 1],
+        };
+    }
+}
+
+/// Times parsing, part 1 and part 2 of a day separately.
+///
+/// A part that fails is reported with its error and not timed any further.
+pub fn bench(day: &Day, text: &str, iterations: u32) -> Result<Vec<Measurement>, AocError> {
+    let iterations = iterations.max(1);
+
+    let mut parse_samples = Vec::new();
+    let mut input = day.parse(text)?;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        input = day.parse(text)?;
+        parse_samples.push(start.elapsed());
+    }
+
+    let mut result = vec![Measurement {
+        stage: "parse",
+        statistics: Ok(Statistics::from_samples(&parse_samples)),
+    }];
+
+    for (stage, part) in [("part1", 1), ("part2", 2)] {
+        let mut samples = Vec::new();
+        let mut failure = None;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let answer = match part {
+                1 => day.part1(input.as_ref()),
+                _ => day.part2(input.as_ref()),
+            };
+            if let Err(error) = answer {
+                failure = Some(error);
+                break;
+            }
+
+            samples.push(start.elapsed());
+        }
+
+        result.push(Measurement {
+            stage,
+            statistics: match failure {
+                Some(error) => Err(error),
+                None => Ok(Statistics::from_samples(&samples)),
+            },
+        });
+    }
+
+    return Ok(result);
+}
+
+pub fn format_text(day: &Day, iterations: u32, measurements: &[Measurement]) -> String {
+    let mut result = format!("Day {} - {iterations} iterations\n", day.number);
+    result.push_str(&format!(
+        "{:<5} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+        "stage", "min", "median", "mean", "max"
+    ));
+
+    for measurement in measurements {
+        match &measurement.statistics {
+            Ok(s) => result.push_str(&format!(
+                "{:<5} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+                measurement.stage,
+                format!("{:.2?}", s.min),
+                format!("{:.2?}", s.median),
+                format!("{:.2?}", s.mean),
+                format!("{:.2?}", s.max)
+            )),
+            Err(AocError::NotImplemented) => result.push_str(&format!(
+                "{:<5} | skipped: not implemented\n",
+                measurement.stage
+            )),
+            Err(error) => result.push_str(&format!(
+                "{:<5} | failed: {}\n",
+                measurement.stage,
+                error.to_string().replace('\n', " ")
+            )),
+        }
+    }
+
+    return result;
+}
+
+/// Formats the measurements as a single JSON object, with all durations in nanoseconds.
+pub fn format_json(day: &Day, iterations: u32, measurements: &[Measurement]) -> String {
+    let stages = measurements
+        .iter()
+        .map(|measurement| match &measurement.statistics {
+            Ok(s) => format!(
+                "{{\"stage\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"max_ns\":{}}}",
+                json::string(measurement.stage),
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.max.as_nanos()
+            ),
+            Err(error) => format!(
+                "{{\"stage\":{},\"error\":{}}}",
+                json::string(measurement.stage),
+                json::string(&error.to_string())
+            ),
+        })
+        .collect::<Vec<String>>();
+
+    return format!(
+        "{{\"day\":{},\"iterations\":{iterations},\"stages\":[{}]}}",
+        day.number,
+        stages.join(",")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::bench::{bench, format_json, format_text, Statistics};
+    use crate::registry::find;
+
+    #[test]
+    fn test_statistics() {
+        let samples = [5, 1, 4, 2].map(Duration::from_millis);
+        let actual = Statistics::from_samples(&samples);
+
+        assert_eq!(
+            actual,
+            Statistics {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                mean: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_statistics_odd_number_of_samples() {
+        let samples = [7, 1, 4].map(Duration::from_millis);
+        let actual = Statistics::from_samples(&samples);
+
+        assert_eq!(actual.median, Duration::from_millis(4));
+        assert_eq!(actual.mean, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_bench() {
+        let day6 = find("day6").unwrap();
+        let measurements = bench(&day6, "Time: 7 15 30\nDistance: 9 40 200", 3).unwrap();
+
+        assert_eq!(measurements.len(), 3);
+        assert_eq!(measurements[0].stage, "parse");
+        assert!(measurements[1].statistics.is_ok());
+        assert!(measurements[2].statistics.is_err());
+
+        let text = format_text(&day6, 3, &measurements);
+        assert!(text.ends_with("part2 | skipped: not implemented\n"));
+
+        let json = format_json(&day6, 3, &measurements);
+        assert!(json.starts_with("{\"day\":6,\"iterations\":3,\"stages\":[{\"stage\":\"parse\""));
+        assert!(json.ends_with("{\"stage\":\"part2\",\"error\":\"not implemented\"}]}"));
+    }
+
+    #[test]
+    fn test_bench_with_invalid_input() {
+        let day7 = find("day7").unwrap();
+
+        assert!(bench(&day7, "32T3K", 3).is_err());
+    }
+}
@@ -1,10 +1,9 @@
-use std::error::Error;
-use std::fs;
-
 use phf::phf_map;
 use substring::Substring;
 
-use crate::GenericError;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 static DIGITS: phf::Map<&'static str, u32> = phf_map! {
     "1" => 1,
@@ -27,38 +26,45 @@ static DIGITS: phf::Map<&'static str, u32> = phf_map! {
     "nine" => 9
 };
 
-pub fn day1(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let digits = find_all_first_and_last_digits(&text);
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        return Ok(input.to_string());
+    }
 
-    if digits.is_none() {
-        return Err(Box::new(GenericError::new(
-            "not all lines contained (at least) two digits",
-        )));
+    fn part1(&self, input: &String) -> Result<Answer, AocError> {
+        return Ok(Answer::from(challenge1(input)?));
     }
+}
+
+pub fn day1(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge1);
+}
 
-    Ok(digits.unwrap().iter().sum())
+pub fn challenge1(input: &str) -> Result<u32, AocError> {
+    let digits = find_all_first_and_last_digits(input)?;
+
+    Ok(digits.iter().sum())
 }
 
-fn find_all_first_and_last_digits(text: &str) -> Option<Vec<u32>> {
+fn find_all_first_and_last_digits(text: &str) -> Result<Vec<u32>, AocError> {
     let mut result: Vec<u32> = Vec::new();
 
-    for (line_number, line) in text.lines().enumerate() {
+    for line in text.lines() {
         let maybe_digits = find_first_and_last_digit(line);
         let maybe_combined_digits = combine_digits(&maybe_digits);
 
         if maybe_combined_digits.is_none() {
-            println!(
-                "Line number {} ('{line}') does not contain two digits.",
-                line_number + 1
-            );
-            return None;
+            return Err(AocError::parse("line does not contain any digit", line).locate(text));
         }
 
         result.push(maybe_combined_digits.unwrap());
     }
 
-    Some(result)
+    Ok(result)
 }
 
 fn combine_digits(digits: &Option<(u32, u32)>) -> Option<u32> {
@@ -112,10 +118,24 @@ fn extract_next_digit(input: &str) -> (&str, Option<u32>) {
 #[cfg(test)]
 mod tests {
     use super::{
-        combine_digits, extract_next_digit, find_all_first_and_last_digits,
+        challenge1, combine_digits, extract_next_digit, find_all_first_and_last_digits,
         find_first_and_last_digit,
     };
 
+    #[test]
+    fn challenge1_example() {
+        let input = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+        assert_eq!(challenge1(input).unwrap(), 281);
+    }
+
     #[test]
     fn multiple_lines() {
         let input = "\
@@ -130,7 +150,7 @@ one
         let actual = find_all_first_and_last_digits(input);
 
         assert_eq!(
-            vec_eq(&vec![12, 34, 56, 79, 11, 14, 11], &actual.unwrap()),
+            vec_eq(&[12, 34, 56, 79, 11, 14, 11], &actual.unwrap()),
             true
         );
     }
@@ -147,7 +167,7 @@ zoneight234
 7pqrstsixteen";
         let actual = find_all_first_and_last_digits(input);
 
-        assert!(vec_eq(&vec![29, 83, 13, 24, 42, 14, 76], &actual.unwrap()))
+        assert!(vec_eq(&[29, 83, 13, 24, 42, 14, 76], &actual.unwrap()))
     }
 
     #[test]
@@ -157,7 +177,26 @@ oneight
 1twone";
         let actual = find_all_first_and_last_digits(input);
 
-        assert!(vec_eq(&vec![18, 11], &actual.unwrap()));
+        assert!(vec_eq(&[18, 11], &actual.unwrap()));
+    }
+
+    #[test]
+    fn line_without_digits() {
+        let input = "\
+12
+foo
+34";
+        let error = find_all_first_and_last_digits(input).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "\
+line does not contain any digit
+ --> <input>:2:1
+  |
+2 | foo
+  | ^^^"
+        );
     }
 
     #[test]
@@ -248,7 +287,7 @@ oneight
         assert_eq!(None, actual);
     }
 
-    fn vec_eq(first: &Vec<u32>, second: &Vec<u32>) -> bool {
+    fn vec_eq(first: &[u32], second: &[u32]) -> bool {
         if first.len() != second.len() {
             return false;
         }
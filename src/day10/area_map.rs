@@ -81,7 +81,7 @@ impl AreaMap {
             return Pipe(loop_map.start_tile_shape);
         }
 
-        return (&loop_map.tile_map.tiles[tile_index]).clone();
+        return loop_map.tile_map.tiles[tile_index];
     }
 }
 
@@ -104,7 +104,7 @@ mod tests {
 .L--J.L--J.
 ...........";
 
-        let tile_map = TileMap::parse(&input).unwrap();
+        let tile_map = TileMap::parse(input).unwrap();
         let loop_map = LoopMap::find_first(&tile_map, &12).unwrap();
         let sut = AreaMap::calculate(&loop_map);
 
@@ -124,7 +124,7 @@ mod tests {
 .L--JL--J.
 ..........";
 
-        let tile_map = TileMap::parse(&input).unwrap();
+        let tile_map = TileMap::parse(input).unwrap();
         let loop_map = LoopMap::find_first(&tile_map, &11).unwrap();
         let sut = AreaMap::calculate(&loop_map);
 
@@ -145,7 +145,7 @@ L--J.L7...LJS7F-7L7.
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
 
-        let tile_map = TileMap::parse(&input).unwrap();
+        let tile_map = TileMap::parse(input).unwrap();
         let loop_map = LoopMap::find_first(&tile_map, &tile_map.start_tile_indices[0]).unwrap();
         let sut = AreaMap::calculate(&loop_map);
 
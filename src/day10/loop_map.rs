@@ -28,12 +28,7 @@ impl LoopMap<'_> {
             is_in_loop.push(false);
         }
 
-        let maybe_path = LoopMap::find_first_recursive(tile_map, vec![*start_tile_index]);
-        if maybe_path.is_none() {
-            return None;
-        }
-
-        let path = maybe_path.unwrap();
+        let path = LoopMap::find_first_recursive(tile_map, vec![*start_tile_index])?;
         let start_tile_shape = LoopMap::get_start_tile_shape(&path)?;
         let length = path.len();
         for index in path {
@@ -61,7 +56,7 @@ impl LoopMap<'_> {
         let neighbors = LoopMap::get_neighbors_excluding(tile_map, current_index, previous_index);
 
         for neighbor in neighbors {
-            if &neighbor == &current_path[0] {
+            if neighbor == current_path[0] {
                 // our next neighbor is the start node, so the loop is complete
                 return Some(current_path);
             }
@@ -83,16 +78,16 @@ impl LoopMap<'_> {
         current_node: &usize,
         previous_node: Option<&usize>,
     ) -> Vec<usize> {
-        return (&tile_map.neighbor_indices[*current_node])
+        return tile_map.neighbor_indices[*current_node]
             .iter()
             .filter(|i| {
                 *i != current_node && (previous_node.is_none() || *i != previous_node.unwrap())
             })
-            .map(|i| *i)
+            .copied()
             .collect();
     }
 
-    fn get_start_tile_shape(path: &Vec<usize>) -> Option<PipeShape> {
+    fn get_start_tile_shape(path: &[usize]) -> Option<PipeShape> {
         let start_index = &path[0];
         let first_neighbor_index = &path[1];
         let second_neighbor_index = &path[path.len() - 1];
@@ -186,7 +181,7 @@ mod tests {
 .L-J.
 .....";
 
-        let tile_map = TileMap::parse(&input).unwrap();
+        let tile_map = TileMap::parse(input).unwrap();
         let sut = LoopMap::find_first(&tile_map, &6).unwrap();
 
         assert_eq!(sut.length, 8);
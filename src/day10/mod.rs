@@ -1,8 +1,9 @@
-use std::fs;
-
 use crate::day10::area_map::AreaMap;
 use crate::day10::loop_map::LoopMap;
 use crate::day10::tile_map::TileMap;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod area_map;
 mod direction;
@@ -10,20 +11,86 @@ mod loop_map;
 mod tile;
 mod tile_map;
 
-pub fn solve_day10(file_path: &str) {
-    let text = fs::read_to_string(file_path).expect("given challenge file cannot be read");
-    let tile_map = TileMap::parse(&text).expect("cannot parse input data");
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = TileMap;
+
+    fn parse(&self, input: &str) -> Result<TileMap, AocError> {
+        return TileMap::parse(input);
+    }
+
+    fn part1(&self, tile_map: &TileMap) -> Result<Answer, AocError> {
+        let loop_map = find_loop(tile_map)?;
+        return Ok(Answer::from(loop_map.length.div_ceil(2)));
+    }
 
+    fn part2(&self, tile_map: &TileMap) -> Result<Answer, AocError> {
+        let loop_map = find_loop(tile_map)?;
+        return Ok(Answer::from(AreaMap::calculate(&loop_map).area_within));
+    }
+}
+
+fn find_loop(tile_map: &TileMap) -> Result<LoopMap<'_>, AocError> {
     if tile_map.start_tile_indices.len() != 1 {
-        panic!("tile map doesn't contain exactly one start tile");
+        return Err(AocError::unsupported_assumption(
+            "tile map doesn't contain exactly one start tile",
+        ));
+    }
+
+    return LoopMap::find_first(tile_map, &tile_map.start_tile_indices[0])
+        .ok_or_else(|| AocError::validation("no loop in tile map found"));
+}
+
+pub fn solve_day10(file_path: &str) -> Result<(Answer, Answer), AocError> {
+    return solve_file(file_path, solve);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer), AocError> {
+    let tile_map = Day10.parse(input)?;
+    return Ok((Day10.part1(&tile_map)?, Day10.part2(&tile_map)?));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day10::{solve, Day10};
+    use crate::solver::{Answer, Solver};
+
+    #[test]
+    fn test_solve_enclosed_area_example() {
+        let input = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let (_, part2) = solve(input).unwrap();
+
+        assert_eq!(part2, Answer::Unsigned(4));
     }
 
-    let loop_map = LoopMap::find_first(&tile_map, &tile_map.start_tile_indices[0])
-        .expect("no loop in tile map found");
+    #[test]
+    fn test_solver() {
+        let input = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+        let tile_map = Day10.parse(input).unwrap();
 
-    let half_length = (loop_map.length as f64 * 0.5f64).ceil() as i128;
-    println!("Day10 - Challenge1: {half_length}");
+        assert_eq!(Day10.part1(&tile_map).unwrap(), Answer::Unsigned(8));
+        assert_eq!(Day10.part2(&tile_map).unwrap(), Answer::Unsigned(1));
+    }
 
-    let area_map = AreaMap::calculate(&loop_map);
-    println!("Day10 - Challenge2: {}", area_map.area_within);
+    #[test]
+    fn test_solver_without_start_tile() {
+        let tile_map = Day10.parse(".F7.\n.LJ.");
+
+        assert!(tile_map.is_err());
+    }
 }
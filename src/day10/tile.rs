@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use lazy_static::lazy_static;
 
 use crate::day10::direction::Direction;
-use crate::GenericError;
+use crate::error::AocError;
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum PipeShape {
@@ -68,7 +68,7 @@ impl PipeShape {
 }
 
 impl Tile {
-    pub fn parse(input: &u8) -> Result<Tile, GenericError> {
+    pub fn parse(input: &u8) -> Result<Tile, AocError> {
         return match input {
             b'|' => Ok(Tile::Pipe(PipeShape::NorthToSouth)),
             b'-' => Ok(Tile::Pipe(PipeShape::WestToEast)),
@@ -78,7 +78,7 @@ impl Tile {
             b'F' => Ok(Tile::Pipe(PipeShape::SouthToEast)),
             b'.' => Ok(Tile::Ground),
             b'S' => Ok(Tile::Start),
-            _ => Err(GenericError::new("unknown tile type")),
+            _ => Err(AocError::parse_byte("unknown tile type", input)),
         };
     }
 
@@ -89,7 +89,10 @@ impl Tile {
             return false;
         }
 
-        return other.directions().contains(&relative_direction.inverse());
+        return other
+            .directions()
+            .iter()
+            .any(|d| relative_direction.connects_to(d));
     }
 
     pub fn directions(&self) -> &Vec<Direction> {
@@ -1,6 +1,6 @@
 use crate::day10::direction::Direction;
 use crate::day10::tile::Tile;
-use crate::GenericError;
+use crate::error::AocError;
 
 pub struct TileMap {
     pub tiles: Vec<Tile>,
@@ -11,7 +11,7 @@ pub struct TileMap {
 }
 
 impl TileMap {
-    pub fn parse(input: &str) -> Result<TileMap, GenericError> {
+    pub fn parse(input: &str) -> Result<TileMap, AocError> {
         let (tiles, number_of_columns, number_of_rows) = TileMap::parse_tiles(input)?;
         let start_tile_indices = TileMap::get_start_tile_indices(&tiles)?;
         let neighbor_indices =
@@ -26,19 +26,19 @@ impl TileMap {
         });
     }
 
-    fn parse_tiles(input: &str) -> Result<(Vec<Tile>, usize, usize), GenericError> {
+    fn parse_tiles(input: &str) -> Result<(Vec<Tile>, usize, usize), AocError> {
         let mut tiles = Vec::new();
         let mut number_of_columns = None;
         let mut number_of_rows = 0usize;
 
         for line in input.lines() {
-            let mut row = TileMap::parse_row(line)?;
+            let mut row = TileMap::parse_row(line).map_err(|e| e.locate(input))?;
             if number_of_columns.is_none() {
                 number_of_columns = Some(row.len());
             }
 
             if row.len() != number_of_columns.unwrap_or(0) {
-                return Err(GenericError::new("all rows must be equally long"));
+                return Err(AocError::parse("all rows must be equally long", line).locate(input));
             }
 
             tiles.append(&mut row);
@@ -48,7 +48,7 @@ impl TileMap {
         return Ok((tiles, number_of_columns.unwrap_or(0), number_of_rows));
     }
 
-    fn parse_row(input: &str) -> Result<Vec<Tile>, GenericError> {
+    fn parse_row(input: &str) -> Result<Vec<Tile>, AocError> {
         let mut result = Vec::new();
         for byte in input.as_bytes() {
             result.push(Tile::parse(byte)?);
@@ -57,7 +57,7 @@ impl TileMap {
         return Ok(result);
     }
 
-    fn get_start_tile_indices(tiles: &Vec<Tile>) -> Result<Vec<usize>, GenericError> {
+    fn get_start_tile_indices(tiles: &[Tile]) -> Result<Vec<usize>, AocError> {
         let mut start_tile_indices = Vec::new();
         for (index, tile) in tiles.iter().enumerate() {
             if let Tile::Start = tile {
@@ -66,14 +66,16 @@ impl TileMap {
         }
 
         if start_tile_indices.is_empty() {
-            return Err(GenericError::new("tile map does not have any start tiles"));
+            return Err(AocError::validation(
+                "tile map does not have any start tiles",
+            ));
         }
 
         return Ok(start_tile_indices);
     }
 
     fn get_neighbor_indices(
-        tiles: &Vec<Tile>,
+        tiles: &[Tile],
         number_of_columns: usize,
         number_of_rows: usize,
     ) -> Vec<Vec<usize>> {
@@ -137,7 +139,7 @@ mod tests {
 .|.|.
 .L-J.
 .....";
-        let actual = TileMap::parse(&input).unwrap();
+        let actual = TileMap::parse(input).unwrap();
 
         assert_eq!(actual.number_of_columns, 5);
         assert_eq!(actual.number_of_rows, 5);
@@ -3,26 +3,24 @@ use crate::day11::node::Pixel;
 
 pub struct ExpandedUniverse<'a> {
     pub image: &'a Image,
-    expansion_factor: usize,
     horizontal_offsets: Vec<usize>,
     vertical_offsets: Vec<usize>,
 }
 
 impl ExpandedUniverse<'_> {
-    pub fn expand(image: &Image, factor: usize) -> ExpandedUniverse {
+    pub fn expand(image: &Image, factor: usize) -> ExpandedUniverse<'_> {
         return ExpandedUniverse {
             image,
-            expansion_factor: factor,
             horizontal_offsets: ExpandedUniverse::calculate_horizontal_offsets(image, factor),
             vertical_offsets: ExpandedUniverse::calculate_vertical_offsets(image, factor),
         };
     }
 
-    pub fn challenge1(image: &Image) -> ExpandedUniverse {
+    pub fn challenge1(image: &Image) -> ExpandedUniverse<'_> {
         return ExpandedUniverse::expand(image, 2);
     }
 
-    pub fn challenge2(image: &Image) -> ExpandedUniverse {
+    pub fn challenge2(image: &Image) -> ExpandedUniverse<'_> {
         return ExpandedUniverse::expand(image, 1_000_000);
     }
 
@@ -81,8 +79,8 @@ impl ExpandedUniverse<'_> {
     pub fn get_2d_index(&self, index_1d: usize) -> (usize, usize) {
         let (initial_x, initial_y) = self.image.get_2d_index(index_1d);
         return (
-            initial_x + &self.vertical_offsets[initial_x],
-            initial_y + &self.horizontal_offsets[initial_y],
+            initial_x + self.vertical_offsets[initial_x],
+            initial_y + self.horizontal_offsets[initial_y],
         );
     }
 }
@@ -106,7 +104,7 @@ mod tests {
 .......#..
 #...#.....";
 
-        let image = Image::parse(&input).unwrap();
+        let image = Image::parse(input).unwrap();
         let sut = ExpandedUniverse::challenge1(&image);
 
         assert_eq!(sut.horizontal_offsets.len(), image.number_of_columns);
@@ -130,7 +128,7 @@ mod tests {
 .......#..
 #...#.....";
 
-        let image = Image::parse(&input).unwrap();
+        let image = Image::parse(input).unwrap();
         let sut = ExpandedUniverse::challenge1(&image);
 
         assert_eq!(sut.get_2d_index(3), (0, 4));
@@ -1,5 +1,5 @@
 use crate::day11::node::Pixel;
-use crate::GenericError;
+use crate::error::AocError;
 
 pub struct Image {
     pub pixels: Vec<Pixel>,
@@ -9,8 +9,14 @@ pub struct Image {
 }
 
 impl Image {
-    pub fn parse(input: &str) -> Result<Image, GenericError> {
+    pub fn parse(input: &str) -> Result<Image, AocError> {
         let (pixels, number_of_rows, number_of_columns) = Image::parse_pixels(input)?;
+        if pixels.is_empty() {
+            return Err(
+                AocError::parse("the image must contain at least one pixel", input).locate(input),
+            );
+        }
+
         let galaxy_indices = Image::extract_galaxy_indices(&pixels);
 
         return Ok(Image {
@@ -21,7 +27,7 @@ impl Image {
         });
     }
 
-    fn parse_pixels(input: &str) -> Result<(Vec<Pixel>, usize, usize), GenericError> {
+    fn parse_pixels(input: &str) -> Result<(Vec<Pixel>, usize, usize), AocError> {
         let mut pixels = Vec::new();
         let mut number_of_rows = 0usize;
         let mut number_of_columns = None;
@@ -31,7 +37,7 @@ impl Image {
             let mut row = Vec::new();
 
             for byte in line.as_bytes() {
-                row.push(Pixel::parse(byte)?);
+                row.push(Pixel::parse(byte).map_err(|e| e.locate(input))?);
             }
 
             if number_of_columns.is_none() {
@@ -39,7 +45,7 @@ impl Image {
             }
 
             if row.len() != number_of_columns.unwrap_or(0) {
-                return Err(GenericError::new("all rows must be equally long"));
+                return Err(AocError::parse("all rows must be equally long", line).locate(input));
             }
 
             pixels.append(&mut row);
@@ -48,7 +54,7 @@ impl Image {
         return Ok((pixels, number_of_rows, number_of_columns.unwrap_or(0)));
     }
 
-    fn extract_galaxy_indices(pixels: &Vec<Pixel>) -> Vec<usize> {
+    fn extract_galaxy_indices(pixels: &[Pixel]) -> Vec<usize> {
         let mut result = Vec::new();
         for (index, pixel) in pixels.iter().enumerate() {
             if let Pixel::Galaxy = pixel {
@@ -92,11 +98,16 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        let sut = Image::parse(&input).unwrap();
+        let sut = Image::parse(input).unwrap();
 
         assert_eq!(sut.get_2d_index(0), (0, 0));
         assert_eq!(sut.get_2d_index(9), (0, 9));
         assert_eq!(sut.get_2d_index(10), (1, 0));
         assert_eq!(sut.get_2d_index(19), (1, 9));
     }
+
+    #[test]
+    fn test_parse_empty_image() {
+        assert!(Image::parse("").is_err());
+    }
 }
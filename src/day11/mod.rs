@@ -1,28 +1,42 @@
 use std::cmp::{max, min};
-use std::fs;
 
 use crate::day11::expanded_universe::ExpandedUniverse;
 use crate::day11::image::Image;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod expanded_universe;
 mod image;
 mod node;
 
-pub fn solve_day11(file_path: &str) {
-    let text = fs::read_to_string(file_path).expect("given challenge file cannot be read");
-    let image = Image::parse(&text).expect("the given input should be a valid image");
-    let universe_challenge1 = ExpandedUniverse::challenge1(&image);
-
-    println!(
-        "Day11 - Challenge1: {}",
-        sum_of_pairwise_distances(&universe_challenge1)
-    );
-
-    let universe_challenge2 = ExpandedUniverse::challenge2(&image);
-    println!(
-        "Day11 - Challenge2: {}",
-        sum_of_pairwise_distances(&universe_challenge2)
-    );
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Image;
+
+    fn parse(&self, input: &str) -> Result<Image, AocError> {
+        return Image::parse(input);
+    }
+
+    fn part1(&self, image: &Image) -> Result<Answer, AocError> {
+        let universe = ExpandedUniverse::challenge1(image);
+        return Ok(Answer::from(sum_of_pairwise_distances(&universe)));
+    }
+
+    fn part2(&self, image: &Image) -> Result<Answer, AocError> {
+        let universe = ExpandedUniverse::challenge2(image);
+        return Ok(Answer::from(sum_of_pairwise_distances(&universe)));
+    }
+}
+
+pub fn solve_day11(file_path: &str) -> Result<(Answer, Answer), AocError> {
+    return solve_file(file_path, solve);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer), AocError> {
+    let image = Day11.parse(input)?;
+    return Ok((Day11.part1(&image)?, Day11.part2(&image)?));
 }
 
 fn sum_of_pairwise_distances(universe: &ExpandedUniverse) -> u128 {
@@ -47,9 +61,10 @@ fn sum_of_pairwise_distances(universe: &ExpandedUniverse) -> u128 {
 mod tests {
     use crate::day11::expanded_universe::ExpandedUniverse;
     use crate::day11::image::Image;
-    use crate::day11::sum_of_pairwise_distances;
+    use crate::day11::{solve, solve_day11, sum_of_pairwise_distances};
+    use crate::solver::Answer;
 
-    static INPUT: &'static str = "\
+    static INPUT: &str = "\
 ...#......
 .......#..
 #.........
@@ -63,7 +78,7 @@ mod tests {
 
     #[test]
     fn test_sum_of_pairwise_distances_expansion_2() {
-        let image = Image::parse(&INPUT).unwrap();
+        let image = Image::parse(INPUT).unwrap();
         let universe = ExpandedUniverse::expand(&image, 2);
 
         let actual = sum_of_pairwise_distances(&universe);
@@ -73,7 +88,7 @@ mod tests {
 
     #[test]
     fn test_sum_of_pairwise_distances_expansion_10() {
-        let image = Image::parse(&INPUT).unwrap();
+        let image = Image::parse(INPUT).unwrap();
         let universe = ExpandedUniverse::expand(&image, 10);
 
         let actual = sum_of_pairwise_distances(&universe);
@@ -83,11 +98,23 @@ mod tests {
 
     #[test]
     fn test_sum_of_pairwise_distances_expansion_100() {
-        let image = Image::parse(&INPUT).unwrap();
+        let image = Image::parse(INPUT).unwrap();
         let universe = ExpandedUniverse::expand(&image, 100);
 
         let actual = sum_of_pairwise_distances(&universe);
 
         assert_eq!(actual, 8410);
     }
+
+    #[test]
+    fn test_solve_example() {
+        let actual = solve(INPUT).unwrap();
+
+        assert_eq!(actual, (Answer::Unsigned(374), Answer::Unsigned(82000210)));
+    }
+
+    #[test]
+    fn test_solve_day11_missing_file() {
+        assert!(solve_day11("day11-does-not-exist.txt").is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::GenericError;
+use crate::error::AocError;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum Pixel {
@@ -7,11 +7,11 @@ pub enum Pixel {
 }
 
 impl Pixel {
-    pub fn parse(input: &u8) -> Result<Pixel, GenericError> {
+    pub fn parse(input: &u8) -> Result<Pixel, AocError> {
         return match input {
             b'.' => Ok(Pixel::EmptySpace),
             b'#' => Ok(Pixel::Galaxy),
-            _ => Err(GenericError::new("unknown node type")),
+            _ => Err(AocError::parse_byte("unknown node type", input)),
         };
     }
 }
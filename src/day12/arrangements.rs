@@ -0,0 +1,141 @@
+use std::fmt::{Display, Formatter};
+
+use crate::day12::challenge1::{candidates, GroupAutomaton, Layer};
+use crate::day12::record_row::RecordRowView;
+use crate::day12::spring::Spring;
+
+/// A valid assignment of every spring of a row, without unknown springs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Arrangement {
+    pub springs: Vec<Spring>,
+}
+
+impl Display for Arrangement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for spring in &self.springs {
+            write!(f, "{spring}")?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// Yields the valid arrangements of a row, trying broken before functioning springs.
+///
+/// Partial arrangements without any valid completion are never visited, because a table over
+/// every spring index already knows which states can still reach the end of the row.
+pub struct Arrangements<'a> {
+    row: &'a dyn RecordRowView,
+    automaton: GroupAutomaton<'a>,
+    // completable[i] tells whether a state at spring index i has a valid completion
+    completable: Vec<Layer<bool>>,
+    // partial arrangements together with their group index and group length
+    stack: Vec<(Vec<Spring>, usize, usize)>,
+}
+
+impl<'a> Arrangements<'a> {
+    pub fn new(row: &'a dyn RecordRowView) -> Arrangements<'a> {
+        let automaton = GroupAutomaton::new(row);
+
+        let mut completable = vec![automaton.end_layer(true)];
+        for spring_index in (0..row.number_of_springs()).rev() {
+            let next_layer = &completable[completable.len() - 1];
+            let layer = automaton.previous_layer(spring_index, next_layer, |a, b| Ok(a || b));
+            completable.push(layer.unwrap());
+        }
+        completable.reverse();
+
+        let stack = if completable[0].get(0, 0) {
+            vec![(Vec::new(), 0, 0)]
+        } else {
+            Vec::new()
+        };
+
+        return Arrangements {
+            row,
+            automaton,
+            completable,
+            stack,
+        };
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Arrangement;
+
+    fn next(&mut self) -> Option<Arrangement> {
+        while let Some((springs, group_index, group_length)) = self.stack.pop() {
+            let spring_index = springs.len();
+            if spring_index == self.row.number_of_springs() {
+                return Some(Arrangement { springs });
+            }
+
+            // pushed in reverse, so that broken springs are popped first
+            for spring in candidates(self.row.get_spring(spring_index)).iter().rev() {
+                let Some((next_group_index, next_group_length)) =
+                    self.automaton.next_state(group_index, group_length, spring)
+                else {
+                    continue;
+                };
+
+                if self.completable[spring_index + 1].get(next_group_index, next_group_length) {
+                    let mut next_springs = springs.clone();
+                    next_springs.push(*spring);
+                    self.stack
+                        .push((next_springs, next_group_index, next_group_length));
+                }
+            }
+        }
+
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::day12::arrangements::Arrangements;
+    use crate::day12::challenge1::solve_challenge1;
+    use crate::day12::record_row::{RecordRow, UnfoldedRecordRowView};
+
+    fn arrangements(input: &str, fold_factor: usize) -> Vec<String> {
+        let record_row = RecordRow::parse(input).unwrap();
+        let view = UnfoldedRecordRowView::new(&record_row, NonZeroUsize::new(fold_factor).unwrap());
+
+        return Arrangements::new(&view).map(|a| a.to_string()).collect();
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let actual = arrangements(".??..??...?##. 1,1,3", 1);
+
+        assert_eq!(
+            actual,
+            vec![
+                ".#...#....###.",
+                ".#....#...###.",
+                "..#..#....###.",
+                "..#...#...###.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrangements_without_valid_arrangement() {
+        assert!(arrangements("#.# 3", 1).is_empty());
+    }
+
+    #[test]
+    fn test_number_of_arrangements_matches_count() {
+        let input = "?###???????? 3,2,1";
+        let record_row = RecordRow::parse(input).unwrap();
+        let view = UnfoldedRecordRowView::new(&record_row, NonZeroUsize::new(2).unwrap());
+
+        assert_eq!(
+            Arrangements::new(&view).count() as u128,
+            solve_challenge1(&view).unwrap()
+        );
+        assert_eq!(arrangements(input, 1)[0], ".###.##.#...");
+    }
+}
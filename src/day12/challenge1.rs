@@ -1,92 +1,233 @@
+use std::ops::Range;
+
 use crate::day12::record_row::RecordRowView;
 use crate::day12::spring::Spring;
+use crate::error::AocError;
+
+/// Counts the valid combinations of a row, failing instead of wrapping when they exceed `u128`.
+pub fn solve_challenge1(record_row: &dyn RecordRowView) -> Result<u128, AocError> {
+    let automaton = GroupAutomaton::new(record_row);
+
+    let mut layer = automaton.end_layer(1u128);
+    for spring_index in (0..record_row.number_of_springs()).rev() {
+        layer = automaton.previous_layer(spring_index, &layer, add_counts)?;
+    }
 
-pub fn solve_challenge1(record_row: &dyn RecordRowView) -> usize {
-    return count_valid_combinations(record_row, 0, 0, 0);
+    return Ok(layer.get(0, 0));
 }
 
-fn count_valid_combinations(
-    row: &dyn RecordRowView,
-    current_spring_index: usize,
-    current_group_index: usize,
-    current_group_length: usize,
-) -> usize {
-    let number_of_groups = row.number_of_groups();
-    let number_of_springs = row.number_of_springs();
-
-    if current_spring_index >= number_of_springs {
-        if current_group_index >= number_of_groups && current_group_length == 0 {
-            // we closed the last group before we reached the end of our sequence
-            return 1;
+pub(super) fn add_counts(left: u128, right: u128) -> Result<u128, AocError> {
+    return left.checked_add(right).ok_or_else(|| {
+        AocError::overflow("the number of arrangements does not fit into 128 bits")
+    });
+}
+
+/// The states of matching a row spring by spring: the number of finished groups and the length
+/// of the current group.
+///
+/// Rows are solved bottom-up from the last spring, one spring index at a time, so neither the
+/// stack nor the memory grow with the length of the row. Only the group indices that can still
+/// be part of a valid arrangement at a spring index are kept, because the finished groups have
+/// to fit before it and the remaining groups after it.
+pub(super) struct GroupAutomaton<'a> {
+    row: &'a dyn RecordRowView,
+    groups: Vec<usize>,
+    longest_group: usize,
+    // the springs needed for the groups before the index, each followed by a functioning spring
+    needed_before: Vec<usize>,
+    // the springs needed for the groups after the index, separated by functioning springs
+    needed_after: Vec<usize>,
+}
+
+/// A value for every state at one spring index, e.g. the number of arrangements from there on.
+pub(super) struct Layer<T> {
+    groups: Range<usize>,
+    group_lengths: usize,
+    values: Vec<T>,
+}
+
+impl<T: Copy + Default> Layer<T> {
+    pub(super) fn get(&self, group_index: usize, group_length: usize) -> T {
+        if !self.groups.contains(&group_index) {
+            return T::default();
+        }
+
+        let index = (group_index - self.groups.start) * self.group_lengths + group_length;
+        return self.values[index];
+    }
+}
+
+impl GroupAutomaton<'_> {
+    pub(super) fn new(row: &dyn RecordRowView) -> GroupAutomaton<'_> {
+        let groups = (0..row.number_of_groups())
+            .map(|index| row.get_group(index))
+            .collect::<Vec<usize>>();
+        let longest_group = groups.iter().copied().max().unwrap_or(0);
+
+        let mut needed_before = vec![0usize];
+        for group in &groups {
+            needed_before.push(needed_before[needed_before.len() - 1] + group + 1);
         }
 
-        if current_group_index + 1 == number_of_groups
-            && current_group_length == row.get_group(current_group_index)
-        {
-            // we closed the last group right at the end of our sequence
-            return 1;
+        let mut needed_after = vec![0usize; groups.len() + 1];
+        for index in (0..groups.len().saturating_sub(1)).rev() {
+            needed_after[index] = needed_after[index + 1] + groups[index + 1] + 1;
         }
 
-        return 0;
+        return GroupAutomaton {
+            row,
+            groups,
+            longest_group,
+            needed_before,
+            needed_after,
+        };
     }
 
-    let current_spring = row.get_spring(current_spring_index);
-    let springs = if let Spring::Unknown = current_spring {
-        vec![&Spring::Broken, &Spring::Functioning]
-    } else {
-        vec![current_spring]
-    };
+    /// The group indices of states that may be part of a valid arrangement at the spring index.
+    fn group_window(&self, spring_index: usize) -> Range<usize> {
+        let remaining = self.row.number_of_springs() - spring_index;
+        let first = self
+            .needed_after
+            .partition_point(|needed| *needed > remaining);
+        let end = self
+            .needed_before
+            .partition_point(|needed| *needed <= spring_index);
+
+        return first..end.max(first);
+    }
+
+    fn layer<T: Copy + Default>(
+        &self,
+        spring_index: usize,
+        value: impl Fn(usize, usize) -> Result<T, AocError>,
+    ) -> Result<Layer<T>, AocError> {
+        let groups = self.group_window(spring_index);
+        let group_lengths = self.longest_group + 1;
+
+        let mut values = Vec::with_capacity(groups.len() * group_lengths);
+        for group_index in groups.clone() {
+            // a group can't be longer than expected, and there is no group after the last one
+            let longest_length = self.groups.get(group_index).copied().unwrap_or(0);
+            for group_length in 0..group_lengths {
+                if group_length > longest_length {
+                    values.push(T::default());
+                } else {
+                    values.push(value(group_index, group_length)?);
+                }
+            }
+        }
+
+        return Ok(Layer {
+            groups,
+            group_lengths,
+            values,
+        });
+    }
+
+    /// The layer after the last spring, with `reached` for the states that finished all groups.
+    pub(super) fn end_layer<T: Copy + Default>(&self, reached: T) -> Layer<T> {
+        let end = self.row.number_of_springs();
+        let layer = self.layer(end, |group_index, group_length| {
+            if self.count_at_end(group_index, group_length) {
+                return Ok(reached);
+            }
+
+            return Ok(T::default());
+        });
+
+        return layer.unwrap();
+    }
+
+    /// The layer at the spring index, combining the values the next layer has for the states
+    /// every candidate of the spring leads to.
+    pub(super) fn previous_layer<T: Copy + Default>(
+        &self,
+        spring_index: usize,
+        next_layer: &Layer<T>,
+        combine: impl Fn(T, T) -> Result<T, AocError>,
+    ) -> Result<Layer<T>, AocError> {
+        let spring = self.row.get_spring(spring_index);
+
+        return self.layer(spring_index, |group_index, group_length| {
+            let mut value = T::default();
+            for candidate in candidates(spring) {
+                if let Some((next_group_index, next_group_length)) =
+                    self.next_state(group_index, group_length, candidate)
+                {
+                    value = combine(value, next_layer.get(next_group_index, next_group_length))?;
+                }
+            }
+
+            return Ok(value);
+        });
+    }
+
+    fn count_at_end(&self, current_group_index: usize, current_group_length: usize) -> bool {
+        if current_group_index >= self.groups.len() && current_group_length == 0 {
+            // we closed the last group before we reached the end of our sequence
+            return true;
+        }
+
+        // we close the last group right at the end of our sequence
+        return current_group_index + 1 == self.groups.len()
+            && current_group_length == self.groups[current_group_index];
+    }
 
-    let mut count = 0usize;
-    for spring in springs {
+    /// The group index and group length after the next spring is `spring`, if that is valid.
+    pub(super) fn next_state(
+        &self,
+        current_group_index: usize,
+        current_group_length: usize,
+        spring: &Spring,
+    ) -> Option<(usize, usize)> {
         if let Spring::Broken = spring {
-            if current_group_index >= number_of_groups {
+            if current_group_index >= self.groups.len() {
                 // too many groups
-                continue;
+                return None;
             }
 
             let next_current_group_length = current_group_length + 1;
-            if next_current_group_length > row.get_group(current_group_index) {
+            if next_current_group_length > self.groups[current_group_index] {
                 // current group is too large
-                continue;
+                return None;
             }
 
-            count += count_valid_combinations(
-                row,
-                current_spring_index + 1,
-                current_group_index,
-                next_current_group_length,
-            );
-        } else {
-            if current_group_length > 0 {
-                // our current group is finished
-
-                if current_group_length != row.get_group(current_group_index) {
-                    // our group didn't match the expectation
-                    continue;
-                }
+            return Some((current_group_index, next_current_group_length));
+        }
 
-                count += count_valid_combinations(
-                    row,
-                    current_spring_index + 1,
-                    current_group_index + 1,
-                    0,
-                );
-            } else {
-                // we are in no group
-                count +=
-                    count_valid_combinations(row, current_spring_index + 1, current_group_index, 0);
+        if current_group_length > 0 {
+            // our current group is finished
+
+            if current_group_length != self.groups[current_group_index] {
+                // our group didn't match the expectation
+                return None;
             }
+
+            return Some((current_group_index + 1, 0));
         }
+
+        // we are in no group
+        return Some((current_group_index, 0));
     }
+}
 
-    return count;
+/// The springs a spring of the record may actually be.
+pub(super) fn candidates(spring: &Spring) -> &'static [Spring] {
+    return match spring {
+        Spring::Unknown => &[Spring::Broken, Spring::Functioning],
+        Spring::Broken => &[Spring::Broken],
+        Spring::Functioning => &[Spring::Functioning],
+    };
 }
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use crate::day12::challenge1::solve_challenge1;
     use crate::day12::record_row::{RecordRow, UnfoldedRecordRowView};
+    use crate::day12::spring::Spring;
+    use crate::error::AocError;
 
     #[test]
     fn test_challenge1_001() {
@@ -196,17 +337,64 @@ mod tests {
         assert_challenge2(input, 506_250);
     }
 
-    fn assert_challenge1(input: &str, expected: usize) {
+    #[test]
+    fn test_challenge2_long_row() {
+        // all 104 springs are unknown, so there are C(90, 15) arrangements of 15 single springs
+        let input = "???????????????????? 1,1,1";
+        assert_challenge2(input, 45_795_673_964_460_816);
+    }
+
+    #[test]
+    fn test_count_past_u64() {
+        // all 204 springs are unknown, so there are C(185, 20) arrangements of 20 single springs
+        let input = "???????????????????????????????????????? 1,1,1,1";
+        assert_challenge2(input, 312_409_969_165_490_058_097_086_670);
+    }
+
+    #[test]
+    fn test_count_past_u128_is_an_overflow() {
+        // 25 copies of 60 unknown springs allow more than 2^128 arrangements of 50 single springs
+        let record_row =
+            RecordRow::parse("???????????????????????????????????????????????????????????? 1,1")
+                .unwrap();
+        let view = UnfoldedRecordRowView::with_joiner(
+            &record_row,
+            NonZeroUsize::new(25).unwrap(),
+            Spring::Unknown,
+        );
+
+        assert!(matches!(
+            solve_challenge1(&view),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_very_long_row() {
+        // two single springs among 100 000 unknown springs, which used to overflow the stack
+        let input = format!("{} 1,1", "?".repeat(100_000));
+        assert_challenge1(&input, 99_999 * 99_998 / 2);
+    }
+
+    #[test]
+    fn test_large_unfold_factor() {
+        let record_row = RecordRow::parse("#.# 1,1").unwrap();
+        let view = UnfoldedRecordRowView::new(&record_row, NonZeroUsize::new(10_000).unwrap());
+
+        assert_eq!(solve_challenge1(&view).unwrap(), 1);
+    }
+
+    fn assert_challenge1(input: &str, expected: u128) {
         let record_row = RecordRow::parse(input).unwrap();
-        let view = UnfoldedRecordRowView::new(&record_row, 1);
+        let view = UnfoldedRecordRowView::new(&record_row, NonZeroUsize::MIN);
 
-        assert_eq!(solve_challenge1(&view), expected);
+        assert_eq!(solve_challenge1(&view).unwrap(), expected);
     }
 
-    fn assert_challenge2(input: &str, expected: usize) {
+    fn assert_challenge2(input: &str, expected: u128) {
         let record_row = RecordRow::parse(input).unwrap();
-        let view = UnfoldedRecordRowView::new(&record_row, 5);
+        let view = UnfoldedRecordRowView::new(&record_row, NonZeroUsize::new(5).unwrap());
 
-        assert_eq!(solve_challenge1(&view), expected);
+        assert_eq!(solve_challenge1(&view).unwrap(), expected);
     }
 }
@@ -1,71 +1,308 @@
-use std::fs;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::available_parallelism;
 
-use crate::day12::challenge1::solve_challenge1;
+use clap::{arg, value_parser, Arg, ArgMatches};
+
+use crate::day12::arrangements::Arrangements;
+use crate::day12::challenge1::{add_counts, solve_challenge1};
 use crate::day12::record_row::{RecordRow, UnfoldedRecordRowView};
+use crate::day12::spring::Spring;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
+mod arrangements;
 mod challenge1;
-mod record_row;
-mod spring;
+pub mod record_row;
+pub mod spring;
+
+/// How often the rows are unfolded for part 2 by the puzzle.
+const DEFAULT_UNFOLD: NonZeroUsize = NonZeroUsize::new(5).unwrap();
 
-pub fn solve_day12(file_path: &str) {
-    let text = fs::read_to_string(file_path).expect("given challenge file cannot be read");
-    let record_rows = RecordRow::parse_all(&text).expect("the given input should be valid");
+pub struct Day12 {
+    /// The number of worker threads for part 2.
+    pub threads: usize,
+    /// The 1-based number of the row whose arrangements are explained instead of solving.
+    pub explain_row: Option<usize>,
+    /// The maximum number of arrangements that are explained.
+    pub explain_limit: usize,
+    /// How often the rows are unfolded, defaults to 5 for part 2 and 1 for explanations.
+    pub unfold: Option<NonZeroUsize>,
+    /// The spring between two copies of an unfolded row.
+    pub joiner: Spring,
+}
 
-    let mut challenge1 = 0usize;
-    for record_row in &record_rows {
-        let view = UnfoldedRecordRowView::new(record_row, 1);
-        let combinations = solve_challenge1(&view);
-        // println!("{combinations}");
-        challenge1 += combinations;
+impl Default for Day12 {
+    fn default() -> Self {
+        return Day12 {
+            threads: available_parallelism().map_or(1, NonZeroUsize::get),
+            explain_row: None,
+            explain_limit: 10,
+            unfold: None,
+            joiner: Spring::Unknown,
+        };
     }
+}
 
-    println!("Day12 - Challenge1: {challenge1}");
+impl Solver for Day12 {
+    type Input = Vec<RecordRow>;
 
-    let challenge2 = solve_challenge2_multi_threaded(&record_rows, 8);
+    fn arguments(&self) -> Vec<Arg> {
+        let threads = arg!(--threads <N> "The number of worker threads, defaults to the available parallelism.")
+            .value_parser(value_parser!(u32).range(1..));
 
-    println!("Day12 - Challenge2: {challenge2}");
-}
+        let unfold = arg!(--unfold <N> "How often rows are unfolded, defaults to 5 for part 2 and 1 for --explain.")
+            .value_parser(value_parser!(u64).range(1..));
+        let joiner = arg!(--joiner <SPRING> "The spring between two copies of an unfolded row.")
+            .value_parser(["?", ".", "#"])
+            .default_value("?");
 
-fn solve_challenge2_multi_threaded(rows: &Vec<RecordRow>, threads: usize) -> usize {
-    crossbeam::scope(|scope| {
-        let mut thread_handles = Vec::new();
-        let mut next_rows_start_index = 0usize;
-        let chunk_size = (rows.len() as f64 / threads as f64).ceil() as usize;
-
-        for i in 0..threads {
-            let my_chunk_size = if i + 1 == threads {
-                rows.len() - next_rows_start_index
-            } else {
-                chunk_size
-            };
-
-            let mut my_rows = Vec::new();
-            for i in next_rows_start_index..next_rows_start_index + my_chunk_size {
-                my_rows.push(&rows[i]);
-            }
-
-            next_rows_start_index += my_chunk_size;
-
-            let handle = scope.spawn(move |_| {
-                let mut result = 0usize;
-                for row in my_rows {
-                    let view = UnfoldedRecordRowView::new(row, 5);
-                    let combinations = solve_challenge1(&view);
-                    result += combinations;
-                }
-
-                return result;
-            });
-
-            thread_handles.push(handle);
+        return vec![threads, unfold, joiner];
+    }
+
+    fn explain_arguments(&self) -> Vec<Arg> {
+        // the explanation is always printed as text
+        let explain =
+            arg!(--explain <LINE> "Print the arrangements of the row on this line instead.")
+                .value_parser(value_parser!(u64).range(1..))
+                .conflicts_with("format");
+        let limit = arg!(--limit <N> "The maximum number of arrangements printed by --explain.")
+            .value_parser(value_parser!(usize))
+            .default_value("10")
+            .requires("explain");
+
+        return vec![explain, limit];
+    }
+
+    fn configure(&mut self, matches: &ArgMatches) {
+        if let Some(threads) = matches.get_one::<u32>("threads") {
+            self.threads = *threads as usize;
+        }
+        self.unfold = matches
+            .get_one::<u64>("unfold")
+            .and_then(|n| NonZeroUsize::new(*n as usize));
+        if let Some(joiner) = matches.get_one::<String>("joiner") {
+            // the possible values are all valid springs
+            self.joiner = Spring::parse(&joiner.as_bytes()[0]).unwrap();
         }
+    }
 
-        let mut result = 0usize;
-        for handle in thread_handles {
-            result += handle.join().unwrap();
+    fn configure_explanation(&mut self, matches: &ArgMatches) {
+        self.explain_row = matches.get_one::<u64>("explain").map(|line| *line as usize);
+        if let Some(limit) = matches.get_one::<usize>("limit") {
+            self.explain_limit = *limit;
         }
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<RecordRow>, AocError> {
+        return RecordRow::parse_all(input);
+    }
+
+    fn explain(&self, record_rows: &Vec<RecordRow>) -> Option<Result<String, AocError>> {
+        let line = self.explain_row?;
+        return Some(explain_row(
+            record_rows,
+            line,
+            self.explain_limit,
+            self.unfold.unwrap_or(NonZeroUsize::MIN),
+            self.joiner,
+        ));
+    }
+
+    fn part1(&self, record_rows: &Vec<RecordRow>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(solve_challenge1_single_threaded(record_rows)?));
+    }
+
+    fn part2(&self, record_rows: &Vec<RecordRow>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(solve_challenge2_multi_threaded(
+            record_rows,
+            self.threads,
+            self.unfold.unwrap_or(DEFAULT_UNFOLD),
+            self.joiner,
+        )?));
+    }
+}
+
+pub fn solve_day12(file_path: &str) -> Result<(Answer, Answer), AocError> {
+    return solve_file(file_path, solve);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer), AocError> {
+    let day12 = Day12::default();
+    let record_rows = day12.parse(input)?;
+    return Ok((day12.part1(&record_rows)?, day12.part2(&record_rows)?));
+}
+
+/// Lists the first `limit` arrangements of the row on the 1-based `line`.
+fn explain_row(
+    rows: &[RecordRow],
+    line: usize,
+    limit: usize,
+    fold_factor: NonZeroUsize,
+    joiner: Spring,
+) -> Result<String, AocError> {
+    let record_row = line
+        .checked_sub(1)
+        .and_then(|index| rows.get(index))
+        .ok_or_else(|| {
+            AocError::validation(format!(
+                "there is no row on line {line}, the input has {} rows",
+                rows.len()
+            ))
+        })?;
+    let view = UnfoldedRecordRowView::with_joiner(record_row, fold_factor, joiner);
+    // the arrangements can still be listed when there are too many to count
+    let count = match solve_challenge1(&view) {
+        Ok(count) => count.to_string(),
+        Err(AocError::Overflow(_)) => String::from("more than 2^128"),
+        Err(error) => return Err(error),
+    };
+
+    let mut result = format!("{record_row}\n");
+    let mut shown = 0usize;
+    for arrangement in Arrangements::new(&view).take(limit) {
+        result.push_str(&format!("{arrangement}\n"));
+        shown += 1;
+    }
+    result.push_str(&format!("showing {shown} of {count} arrangements\n"));
 
-        return result;
+    return Ok(result);
+}
+
+fn solve_challenge1_single_threaded(rows: &[RecordRow]) -> Result<u128, AocError> {
+    let mut challenge1 = 0u128;
+    for record_row in rows {
+        let view = UnfoldedRecordRowView::new(record_row, NonZeroUsize::MIN);
+        let combinations = solve_challenge1(&view)?;
+        challenge1 = add_counts(challenge1, combinations)?;
+    }
+
+    return Ok(challenge1);
+}
+
+/// Solves the rows on `threads` workers, each taking the next unsolved row when it is idle.
+///
+/// A single thread solves all rows on the calling thread, which keeps profiles deterministic.
+fn solve_challenge2_multi_threaded(
+    rows: &[RecordRow],
+    threads: usize,
+    fold_factor: NonZeroUsize,
+    joiner: Spring,
+) -> Result<u128, AocError> {
+    let solve_row = |row: &RecordRow| {
+        solve_challenge1(&UnfoldedRecordRowView::with_joiner(
+            row,
+            fold_factor,
+            joiner,
+        ))
+    };
+
+    if threads <= 1 {
+        return rows
+            .iter()
+            .try_fold(0u128, |sum, row| add_counts(sum, solve_row(row)?));
+    }
+
+    let next_row_index = AtomicUsize::new(0);
+    crossbeam::scope(|scope| {
+        let thread_handles = (0..threads.min(rows.len()))
+            .map(|_| {
+                scope.spawn(|_| {
+                    let mut result = 0u128;
+                    loop {
+                        let index = next_row_index.fetch_add(1, Ordering::Relaxed);
+                        match rows.get(index) {
+                            Some(row) => result = add_counts(result, solve_row(row)?)?,
+                            None => return Ok(result),
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        return thread_handles.into_iter().try_fold(0u128, |sum, handle| {
+            add_counts(sum, handle.join().unwrap()?)
+        });
     })
     .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::day12::spring::Spring;
+    use crate::day12::{
+        explain_row, solve, solve_challenge2_multi_threaded, Day12, DEFAULT_UNFOLD,
+    };
+    use crate::solver::{Answer, Solver};
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_solve_example() {
+        let actual = solve(EXAMPLE).unwrap();
+
+        assert_eq!(actual, (Answer::Unsigned(21), Answer::Unsigned(525152)));
+    }
+
+    #[test]
+    fn test_explain_row() {
+        let record_rows = Day12::default().parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            explain_row(&record_rows, 2, 3, NonZeroUsize::MIN, Spring::Unknown).unwrap(),
+            "\
+.??..??...?##. 1,1,3
+.#...#....###.
+.#....#...###.
+..#..#....###.
+showing 3 of 4 arrangements
+"
+        );
+        assert!(explain_row(&record_rows, 7, 3, NonZeroUsize::MIN, Spring::Unknown).is_err());
+    }
+
+    #[test]
+    fn test_challenge2_with_unfold_and_joiner() {
+        let record_rows = Day12::default().parse("???.### 1,1,3").unwrap();
+
+        let day12 = Day12 {
+            unfold: NonZeroUsize::new(2),
+            ..Day12::default()
+        };
+        assert_eq!(day12.part2(&record_rows).unwrap(), Answer::Unsigned(1));
+
+        let day12 = Day12 {
+            unfold: NonZeroUsize::new(2),
+            joiner: Spring::Broken,
+            ..Day12::default()
+        };
+        assert_eq!(day12.part2(&record_rows).unwrap(), Answer::Unsigned(0));
+    }
+
+    #[test]
+    fn test_challenge2_is_independent_of_threads() {
+        let record_rows = Day12::default().parse(EXAMPLE).unwrap();
+
+        for threads in [1, 2, 3, 16] {
+            assert_eq!(
+                solve_challenge2_multi_threaded(
+                    &record_rows,
+                    threads,
+                    DEFAULT_UNFOLD,
+                    Spring::Unknown
+                )
+                .unwrap(),
+                525152
+            );
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
 
 use crate::day12::spring::Spring;
+use crate::error::AocError;
 use crate::string_functions::{all_to_usize, split_and_clean};
-use crate::GenericError;
 
 pub trait RecordRowView {
     fn number_of_springs(&self) -> usize;
@@ -11,39 +12,56 @@ pub trait RecordRowView {
     fn get_group(&self, index: usize) -> usize;
 }
 
+/// A row repeated `fold_factor` times, with a `joiner` spring between the copies.
 pub struct UnfoldedRecordRowView<'a> {
     row: &'a RecordRow,
-    fold_factor: usize,
+    fold_factor: NonZeroUsize,
+    joiner: Spring,
 }
 
 impl RecordRowView for UnfoldedRecordRowView<'_> {
     fn number_of_springs(&self) -> usize {
-        return self.row.springs.len() * self.fold_factor + self.fold_factor - 1;
-        // add `fold_factor - 1` unknown springs
+        let fold_factor = self.fold_factor.get();
+        return self.row.springs.len() * fold_factor + fold_factor - 1;
+        // add `fold_factor - 1` joiner springs
     }
 
     fn get_spring(&self, index: usize) -> &Spring {
         let relative_index = index % (self.row.springs.len() + 1);
         if relative_index == self.row.springs.len() {
-            return &Spring::Unknown;
+            return &self.joiner;
         }
 
         return &self.row.springs[relative_index];
     }
 
     fn number_of_groups(&self) -> usize {
-        return self.row.broken_spring_groups.len() * self.fold_factor;
+        return self.row.broken_spring_groups.len() * self.fold_factor.get();
     }
 
     fn get_group(&self, index: usize) -> usize {
         let relative_index = index % self.row.broken_spring_groups.len();
-        return *&self.row.broken_spring_groups[relative_index];
+        return self.row.broken_spring_groups[relative_index];
     }
 }
 
 impl UnfoldedRecordRowView<'_> {
-    pub fn new(row: &RecordRow, fold_factor: usize) -> UnfoldedRecordRowView {
-        return UnfoldedRecordRowView { row, fold_factor };
+    /// Unfolds the row the way the puzzle does, joining the copies with unknown springs.
+    pub fn new(row: &RecordRow, fold_factor: NonZeroUsize) -> UnfoldedRecordRowView<'_> {
+        return UnfoldedRecordRowView::with_joiner(row, fold_factor, Spring::Unknown);
+    }
+
+    /// Unfolds the row `fold_factor` times.
+    pub fn with_joiner(
+        row: &RecordRow,
+        fold_factor: NonZeroUsize,
+        joiner: Spring,
+    ) -> UnfoldedRecordRowView<'_> {
+        return UnfoldedRecordRowView {
+            row,
+            fold_factor,
+            joiner,
+        };
     }
 }
 
@@ -53,24 +71,24 @@ pub struct RecordRow {
 }
 
 impl RecordRow {
-    pub fn parse_all(input: &str) -> Result<Vec<RecordRow>, GenericError> {
+    pub fn parse_all(input: &str) -> Result<Vec<RecordRow>, AocError> {
         let mut result = Vec::new();
         for line in input.lines() {
-            result.push(RecordRow::parse(line)?);
+            result.push(RecordRow::parse(line).map_err(|e| e.locate(input))?);
         }
 
         return Ok(result);
     }
 
-    pub fn parse(input: &str) -> Result<RecordRow, GenericError> {
+    pub fn parse(input: &str) -> Result<RecordRow, AocError> {
         let parts = split_and_clean(input, " ");
         if parts.len() != 2 {
-            return Err(GenericError::new("incorrect record row format"));
+            return Err(AocError::parse("incorrect record row format", input));
         }
 
-        let springs = RecordRow::parse_springs(&parts[0])?;
+        let springs = RecordRow::parse_springs(parts[0])?;
 
-        let raw_spring_groups = split_and_clean(&parts[1], ",");
+        let raw_spring_groups = split_and_clean(parts[1], ",");
         let broken_spring_groups = all_to_usize(&raw_spring_groups)?;
 
         return Ok(RecordRow {
@@ -79,7 +97,7 @@ impl RecordRow {
         });
     }
 
-    fn parse_springs(input: &str) -> Result<Vec<Spring>, GenericError> {
+    fn parse_springs(input: &str) -> Result<Vec<Spring>, AocError> {
         let mut result = Vec::new();
         for byte in input.as_bytes() {
             result.push(Spring::parse(byte)?);
@@ -111,15 +129,17 @@ impl Display for RecordRow {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use crate::day12::record_row::{RecordRow, RecordRowView, UnfoldedRecordRowView};
     use crate::day12::spring::Spring;
 
     #[test]
     fn test_number_of_unfolded_springs_001() {
         let input = ".# 1";
-        let row = RecordRow::parse(&input).unwrap();
+        let row = RecordRow::parse(input).unwrap();
 
-        let sut = UnfoldedRecordRowView::new(&row, 1);
+        let sut = UnfoldedRecordRowView::new(&row, NonZeroUsize::MIN);
 
         assert_eq!(sut.number_of_springs(), 2);
         assert_eq!(sut.number_of_groups(), 1);
@@ -132,9 +152,9 @@ mod tests {
     #[test]
     fn test_number_of_unfolded_springs_002() {
         let input = ".# 1";
-        let row = RecordRow::parse(&input).unwrap();
+        let row = RecordRow::parse(input).unwrap();
 
-        let sut = UnfoldedRecordRowView::new(&row, 5);
+        let sut = UnfoldedRecordRowView::new(&row, NonZeroUsize::new(5).unwrap());
 
         assert_eq!(sut.number_of_springs(), 14);
         assert_eq!(sut.number_of_groups(), 5);
@@ -151,4 +171,21 @@ mod tests {
         assert_eq!(sut.get_group(1), 1);
         assert_eq!(sut.get_group(2), 1);
     }
+
+    #[test]
+    fn test_unfolded_springs_with_joiner() {
+        let input = ".# 1";
+        let row = RecordRow::parse(input).unwrap();
+
+        let sut = UnfoldedRecordRowView::with_joiner(
+            &row,
+            NonZeroUsize::new(3).unwrap(),
+            Spring::Functioning,
+        );
+
+        assert_eq!(sut.number_of_springs(), 8);
+        assert_eq!(sut.get_spring(2), &Spring::Functioning);
+        assert_eq!(sut.get_spring(5), &Spring::Functioning);
+        assert_eq!(sut.get_spring(7), &Spring::Broken);
+    }
 }
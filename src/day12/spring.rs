@@ -1,4 +1,4 @@
-use crate::GenericError;
+use crate::error::AocError;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -9,12 +9,12 @@ pub enum Spring {
 }
 
 impl Spring {
-    pub fn parse(input: &u8) -> Result<Spring, GenericError> {
+    pub fn parse(input: &u8) -> Result<Spring, AocError> {
         return match input {
             b'.' => Ok(Spring::Functioning),
             b'#' => Ok(Spring::Broken),
             b'?' => Ok(Spring::Unknown),
-            _ => Err(GenericError::new("unknown spring type")),
+            _ => Err(AocError::parse_byte("unknown spring type", input)),
         };
     }
 }
@@ -1,9 +1,9 @@
 use std::cmp::max;
-use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
-use std::fs;
 
-use crate::GenericError;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 struct CubeCollection {
     red: u32,
@@ -12,9 +12,9 @@ struct CubeCollection {
 }
 
 impl CubeCollection {
-    fn parse(input: &str) -> Result<CubeCollection, GenericError> {
+    fn parse(input: &str) -> Result<CubeCollection, AocError> {
         let mut parts = input.split(",").map(|s| s.trim()).collect::<Vec<&str>>();
-        parts.retain(|s| s.len() > 0);
+        parts.retain(|s| !s.is_empty());
 
         let mut red: Option<u32> = None;
         let mut green: Option<u32> = None;
@@ -22,10 +22,13 @@ impl CubeCollection {
 
         for part in parts {
             let mut sub_parts = part.split(" ").map(|s| s.trim()).collect::<Vec<&str>>();
-            sub_parts.retain(|s| s.len() > 0);
+            sub_parts.retain(|s| !s.is_empty());
 
             if sub_parts.len() % 2 != 0 {
-                return Err(GenericError::new("input does not match the assumed format"));
+                return Err(AocError::parse(
+                    "input does not match the assumed format",
+                    part,
+                ));
             }
 
             for i in (0..sub_parts.len()).step_by(2) {
@@ -33,21 +36,28 @@ impl CubeCollection {
                 let color = sub_parts[i + 1];
 
                 if maybe_amount.is_err() {
-                    return Err(GenericError::new("cannot extract amount from given input"));
+                    return Err(AocError::parse(
+                        "cannot extract amount from given input",
+                        sub_parts[i],
+                    ));
                 }
 
                 match color {
                     "red" => {
                         if red.is_some() {
-                            return Err(GenericError::new("red cubes are revealed more than once"));
+                            return Err(AocError::parse(
+                                "red cubes are revealed more than once",
+                                color,
+                            ));
                         }
 
                         red = Some(maybe_amount.unwrap());
                     }
                     "green" => {
                         if green.is_some() {
-                            return Err(GenericError::new(
+                            return Err(AocError::parse(
                                 "green cubes are revealed more than once",
+                                color,
                             ));
                         }
 
@@ -55,15 +65,16 @@ impl CubeCollection {
                     }
                     "blue" => {
                         if blue.is_some() {
-                            return Err(GenericError::new(
+                            return Err(AocError::parse(
                                 "blue cubes are revealed more than once",
+                                color,
                             ));
                         }
 
                         blue = Some(maybe_amount.unwrap());
                     }
                     _ => {
-                        return Err(GenericError::new("unknown color"));
+                        return Err(AocError::parse("unknown color", color));
                     }
                 }
             }
@@ -105,23 +116,24 @@ impl Debug for CubeCollection {
 
 impl Eq for CubeCollection {}
 
-struct Game {
+pub struct Game {
     id: u32,
     reveals: Vec<CubeCollection>,
 }
 
 impl Game {
-    fn parse(input: &str) -> Result<Game, GenericError> {
+    fn parse(input: &str) -> Result<Game, AocError> {
         let mut parts = input
             .trim()
             .split(":")
             .map(|s| s.trim())
             .collect::<Vec<&str>>();
-        parts.retain(|s| s.len() > 0);
+        parts.retain(|s| !s.is_empty());
 
         if parts.len() != 2 {
-            return Err(GenericError::new(
+            return Err(AocError::parse(
                 "the input does not match the assumed format",
+                input,
             ));
         }
 
@@ -131,36 +143,39 @@ impl Game {
         });
     }
 
-    fn extract_game_id(input: &str) -> Result<u32, GenericError> {
+    fn extract_game_id(input: &str) -> Result<u32, AocError> {
         let mut parts = input
             .trim()
             .split(" ")
             .map(|s| s.trim())
             .collect::<Vec<&str>>();
-        parts.retain(|s| s.len() > 0);
+        parts.retain(|s| !s.is_empty());
 
         if parts.len() != 2 {
-            return Err(GenericError::new("unable to extract game id"));
+            return Err(AocError::parse("unable to extract game id", input));
         }
 
         let maybe_id = parts[1].parse::<u32>();
         if maybe_id.is_err() {
-            return Err(GenericError::new("the game id is not a number"));
+            return Err(AocError::parse("the game id is not a number", parts[1]));
         }
 
         return Ok(maybe_id.unwrap());
     }
 
-    fn extract_cube_reveals(input: &str) -> Result<Vec<CubeCollection>, GenericError> {
+    fn extract_cube_reveals(input: &str) -> Result<Vec<CubeCollection>, AocError> {
         let mut parts = input
             .trim()
             .split(";")
             .map(|s| s.trim())
             .collect::<Vec<&str>>();
-        parts.retain(|s| s.len() > 0);
+        parts.retain(|s| !s.is_empty());
 
-        if parts.len() < 1 {
-            return Err(GenericError::new("game does not include any cube reveals"));
+        if parts.is_empty() {
+            return Err(AocError::parse(
+                "game does not include any cube reveals",
+                input,
+            ));
         }
 
         let mut result: Vec<CubeCollection> = Vec::new();
@@ -211,24 +226,57 @@ impl Debug for Game {
 
 impl Eq for Game {}
 
-pub fn day2_challenge1(file_path: &str) -> Result<u32, Box<dyn Error>> {
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<Game>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>, AocError> {
+        return parse_all_games(input);
+    }
+
+    fn part1(&self, games: &Vec<Game>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_possible_game_ids(games)));
+    }
+
+    fn part2(&self, games: &Vec<Game>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_minimum_bag_powers(games)));
+    }
+}
+
+pub fn day2_challenge1(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn day2_challenge2(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge2);
+}
+
+pub fn challenge1(input: &str) -> Result<u32, AocError> {
+    let games = parse_all_games(input)?;
+    return Ok(sum_of_possible_game_ids(&games));
+}
+
+pub fn challenge2(input: &str) -> Result<u32, AocError> {
+    let games = parse_all_games(input)?;
+    return Ok(sum_of_minimum_bag_powers(&games));
+}
+
+fn sum_of_possible_game_ids(games: &[Game]) -> u32 {
     let bag = CubeCollection {
         red: 12,
         green: 13,
         blue: 14,
     };
-    let games = read_all_games(file_path)?;
 
-    return Ok(games
+    return games
         .iter()
         .filter(|g| g.is_possible_with_bag(&bag))
         .map(|g| g.id)
-        .sum());
+        .sum();
 }
 
-pub fn day2_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let games = read_all_games(file_path)?;
-
+fn sum_of_minimum_bag_powers(games: &[Game]) -> u32 {
     let mut result: u32 = 0;
     for game in games {
         let required_bag = game.maximum_number_of_seen_cubes_per_color();
@@ -236,22 +284,13 @@ pub fn day2_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
         result += product;
     }
 
-    return Ok(result);
+    return result;
 }
 
-fn read_all_games(file_path: &str) -> Result<Vec<Game>, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-
+fn parse_all_games(text: &str) -> Result<Vec<Game>, AocError> {
     let mut games: Vec<Game> = Vec::new();
     for line in text.lines() {
-        let maybe_game = Game::parse(line);
-        if maybe_game.is_err() {
-            return Err(Box::new(GenericError::new(
-                "not all lines contain a valid game",
-            )));
-        }
-
-        games.push(maybe_game.unwrap());
+        games.push(Game::parse(line).map_err(|e| e.locate(text))?);
     }
 
     return Ok(games);
@@ -259,7 +298,25 @@ fn read_all_games(file_path: &str) -> Result<Vec<Game>, Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::day2::{CubeCollection, Game};
+    use crate::day2::{challenge1, challenge2, parse_all_games, CubeCollection, Game};
+    use crate::error::AocError;
+
+    static EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn challenge2_example() {
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 2286);
+    }
 
     #[test]
     fn parse_cube_reveal_one_blue() {
@@ -376,6 +433,21 @@ mod tests {
         assert_eq!(actual.is_err(), true)
     }
 
+    #[test]
+    fn parse_all_games_reports_location() {
+        let input = "Game 1: 1 blue\nGame 2: 3 red; 4 purple";
+        let actual = parse_all_games(input);
+
+        match actual {
+            Err(AocError::Parse(error)) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.column, Some(18));
+                assert_eq!(error.snippet, "purple");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
     #[test]
     fn parse_game_simple_case() {
         let input = "Game 1: 1 blue, 2 green, 3 red; 4 blue, 5 red";
@@ -1,29 +1,59 @@
-use std::error::Error;
-use std::fs;
-
 use crate::day3::row_slice::RowSlice;
 use crate::day3::schema::Schema;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod row_slice;
 mod schema;
 mod symbol;
 
-pub fn day3_challenge1(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let schema = Schema::parse(&text)?;
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Schema;
+
+    fn parse(&self, input: &str) -> Result<Schema, AocError> {
+        return Schema::parse(input);
+    }
+
+    fn part1(&self, schema: &Schema) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_part_numbers(schema)));
+    }
+
+    fn part2(&self, schema: &Schema) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_gear_ratios(schema)?));
+    }
+}
+
+pub fn day3_challenge1(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn day3_challenge2(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge2);
+}
 
-    return Ok(schema
+pub fn challenge1(input: &str) -> Result<u32, AocError> {
+    let schema = Schema::parse(input)?;
+    return Ok(sum_of_part_numbers(&schema));
+}
+
+pub fn challenge2(input: &str) -> Result<u32, AocError> {
+    let schema = Schema::parse(input)?;
+    return sum_of_gear_ratios(&schema);
+}
+
+fn sum_of_part_numbers(schema: &Schema) -> u32 {
+    return schema
         .extract_number_slices()
         .iter()
         .filter(|slice| slice.is_part_number())
         .map(|slice| slice.to_number())
-        .sum());
+        .sum();
 }
 
-pub fn day3_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let schema = Schema::parse(&text)?;
-
+fn sum_of_gear_ratios(schema: &Schema) -> Result<u32, AocError> {
     let number_slices = schema.extract_number_slices();
     let mut result: u32 = 0;
 
@@ -46,7 +76,7 @@ pub fn day3_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
 }
 
 fn adjacent_slices<'a>(
-    number_slices: &'a Vec<RowSlice>,
+    number_slices: &'a [RowSlice],
     row: usize,
     column: usize,
 ) -> Vec<&'a RowSlice<'a>> {
@@ -55,3 +85,30 @@ fn adjacent_slices<'a>(
         .filter(|slice| slice.is_adjacent_to_cell(row, column))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::day3::{challenge1, challenge2};
+
+    static EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 4361);
+    }
+
+    #[test]
+    fn test_challenge2_example() {
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 467835);
+    }
+}
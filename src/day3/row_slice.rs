@@ -2,7 +2,7 @@ use std::cmp::{max, min};
 
 use crate::day3::schema::Schema;
 use crate::day3::symbol::Symbol;
-use crate::GenericError;
+use crate::error::AocError;
 
 #[derive(Debug, PartialEq)]
 pub struct RowSlice<'a> {
@@ -13,7 +13,7 @@ pub struct RowSlice<'a> {
 }
 
 impl RowSlice<'_> {
-    pub fn new(schema: &Schema, row: usize, start: usize, end: usize) -> RowSlice {
+    pub fn new(schema: &Schema, row: usize, start: usize, end: usize) -> RowSlice<'_> {
         RowSlice {
             schema,
             row,
@@ -27,10 +27,9 @@ impl RowSlice<'_> {
     }
 
     pub fn is_number(&self) -> bool {
-        self.symbols().iter().all(|s| match s {
-            Symbol::Number(_) => true,
-            _ => false,
-        })
+        self.symbols()
+            .iter()
+            .all(|s| matches!(s, Symbol::Number(_)))
     }
 
     pub fn to_number(&self) -> u32 {
@@ -38,7 +37,7 @@ impl RowSlice<'_> {
             .expect("slice is expected to be a numer")
     }
 
-    pub fn try_to_number(&self) -> Result<u32, GenericError> {
+    pub fn try_to_number(&self) -> Result<u32, AocError> {
         let symbols = self.symbols();
         let mut exponent: u32 = (symbols.len() - 1) as u32;
         let base: u32 = 10;
@@ -48,12 +47,9 @@ impl RowSlice<'_> {
             match *symbol {
                 Symbol::Number(value) => {
                     result += base.pow(exponent) * (value as u32);
-
-                    if exponent > 0 {
-                        exponent -= 1;
-                    }
+                    exponent = exponent.saturating_sub(1);
                 }
-                _ => return Err(GenericError::new("row slice is not a number")),
+                _ => return Err(AocError::validation("row slice is not a number")),
             }
         }
 
@@ -65,11 +61,7 @@ impl RowSlice<'_> {
             return false;
         }
 
-        let adjacent_start = if self.start > 0 {
-            self.start - 1
-        } else {
-            self.start
-        };
+        let adjacent_start = self.start.saturating_sub(1);
         let adjacent_end = if self.end + 1 < self.schema.columns {
             self.end + 1
         } else {
@@ -77,44 +69,34 @@ impl RowSlice<'_> {
         };
 
         // check row above
-        if self.row > 0 {
-            if self.schema.symbols[self.row - 1][adjacent_start..adjacent_end]
+        if self.row > 0
+            && self.schema.symbols[self.row - 1][adjacent_start..adjacent_end]
                 .iter()
-                .any(|s| match s {
-                    Symbol::Other(_) => true,
-                    _ => false,
-                })
-            {
-                return true;
-            }
+                .any(|s| matches!(s, Symbol::Other(_)))
+        {
+            return true;
         }
 
         // check row below
-        if self.row + 1 < self.schema.rows {
-            if self.schema.symbols[self.row + 1][adjacent_start..adjacent_end]
+        if self.row + 1 < self.schema.rows
+            && self.schema.symbols[self.row + 1][adjacent_start..adjacent_end]
                 .iter()
-                .any(|s| match s {
-                    Symbol::Other(_) => true,
-                    _ => false,
-                })
-            {
-                return true;
-            }
+                .any(|s| matches!(s, Symbol::Other(_)))
+        {
+            return true;
         }
 
         // check left
         if self.start > 0 {
-            match self.schema.symbols[self.row][self.start - 1] {
-                Symbol::Other(_) => return true,
-                _ => {}
+            if let Symbol::Other(_) = self.schema.symbols[self.row][self.start - 1] {
+                return true;
             }
         }
 
         // check right
         if self.end < self.schema.columns {
-            match self.schema.symbols[self.row][self.end] {
-                Symbol::Other(_) => return true,
-                _ => {}
+            if let Symbol::Other(_) = self.schema.symbols[self.row][self.end] {
+                return true;
             }
         }
 
@@ -127,11 +109,7 @@ impl RowSlice<'_> {
             return false;
         }
 
-        let adjacent_start = if self.start > 0 {
-            self.start - 1
-        } else {
-            self.start
-        };
+        let adjacent_start = self.start.saturating_sub(1);
 
         return column >= adjacent_start && column <= self.end;
     }
@@ -1,5 +1,6 @@
 use crate::day3::row_slice::RowSlice;
-use crate::GenericError;
+use crate::error::AocError;
+use crate::string_functions::first_non_ascii;
 
 use crate::day3::symbol::Symbol;
 
@@ -11,11 +12,12 @@ pub struct Schema {
 }
 
 impl Schema {
-    pub fn parse(input: &str) -> Result<Schema, GenericError> {
-        if !input.is_ascii() {
-            return Err(GenericError::new(
-                "the input must consist of ascii chars only",
-            ));
+    pub fn parse(input: &str) -> Result<Schema, AocError> {
+        if let Some(non_ascii) = first_non_ascii(input) {
+            return Err(
+                AocError::parse("the input must consist of ascii chars only", non_ascii)
+                    .locate(input),
+            );
         }
 
         let mut symbols: Vec<Vec<Symbol>> = Vec::new();
@@ -27,7 +29,7 @@ impl Schema {
             }
 
             if columns.unwrap_or(0) != bytes.len() {
-                return Err(GenericError::new("all lines must be equally long"));
+                return Err(AocError::parse("all lines must be equally long", line).locate(input));
             }
 
             symbols.push(Symbol::parse_all(bytes));
@@ -40,7 +42,7 @@ impl Schema {
         })
     }
 
-    pub fn extract_number_slices(&self) -> Vec<RowSlice> {
+    pub fn extract_number_slices(&self) -> Vec<RowSlice<'_>> {
         let mut result: Vec<RowSlice> = Vec::new();
         for row in 0..self.rows {
             let mut slices = self.extract_number_slices_from_row(row);
@@ -50,7 +52,7 @@ impl Schema {
         return result;
     }
 
-    fn extract_number_slices_from_row(&self, row: usize) -> Vec<RowSlice> {
+    fn extract_number_slices_from_row(&self, row: usize) -> Vec<RowSlice<'_>> {
         let mut result: Vec<RowSlice> = Vec::new();
 
         let mut sequence_start: Option<usize> = None;
@@ -63,23 +65,18 @@ impl Schema {
                     }
                 }
                 _ => {
-                    if sequence_start.is_some() {
+                    if let Some(start) = sequence_start {
                         // end current sequence
-                        result.push(RowSlice::new(self, row, sequence_start.unwrap(), i));
+                        result.push(RowSlice::new(self, row, start, i));
                         sequence_start = None;
                     }
                 }
             }
         }
 
-        if sequence_start.is_some() {
+        if let Some(start) = sequence_start {
             // end last sequence
-            result.push(RowSlice::new(
-                self,
-                row,
-                sequence_start.unwrap(),
-                self.columns,
-            ));
+            result.push(RowSlice::new(self, row, start, self.columns));
         }
 
         return result;
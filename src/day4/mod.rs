@@ -1,23 +1,54 @@
 use std::cmp::min;
-use std::error::Error;
-use std::fs;
 
 use crate::day4::scratch_card::ScratchCard;
-use crate::GenericError;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod scratch_card;
 
-pub fn day4_challenge1(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let cards = ScratchCard::parse_all(&text)?;
+pub struct Day4;
 
-    return Ok(cards.iter().map(|g| g.points()).sum());
+impl Solver for Day4 {
+    type Input = Vec<ScratchCard>;
+
+    fn parse(&self, input: &str) -> Result<Vec<ScratchCard>, AocError> {
+        return ScratchCard::parse_all(input);
+    }
+
+    fn part1(&self, cards: &Vec<ScratchCard>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_points(cards)));
+    }
+
+    fn part2(&self, cards: &Vec<ScratchCard>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_number_of_cards(cards)?));
+    }
+}
+
+pub fn day4_challenge1(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn day4_challenge2(file_path: &str) -> Result<u32, AocError> {
+    return solve_file(file_path, challenge2);
+}
+
+pub fn challenge1(input: &str) -> Result<u32, AocError> {
+    let cards = ScratchCard::parse_all(input)?;
+    return Ok(total_points(&cards));
 }
 
-pub fn day4_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let initial_cards = ScratchCard::parse_all(&text)?;
-    assert_sequential_ids(&initial_cards)?;
+pub fn challenge2(input: &str) -> Result<u32, AocError> {
+    let initial_cards = ScratchCard::parse_all(input)?;
+    return total_number_of_cards(&initial_cards);
+}
+
+fn total_points(cards: &[ScratchCard]) -> u32 {
+    return cards.iter().map(|g| g.points()).sum();
+}
+
+fn total_number_of_cards(initial_cards: &[ScratchCard]) -> Result<u32, AocError> {
+    assert_sequential_ids(initial_cards)?;
 
     let mut remaining_cards: Vec<&ScratchCard> = initial_cards.iter().collect();
 
@@ -31,23 +62,47 @@ pub fn day4_challenge2(file_path: &str) -> Result<u32, Box<dyn Error>> {
             initial_cards.len(),
             copy_start_index + next_card.number_of_matches as usize,
         );
-        for i in copy_start_index..copy_end_index {
-            remaining_cards.push(&initial_cards[i]);
+        for card in &initial_cards[copy_start_index..copy_end_index] {
+            remaining_cards.push(card);
         }
     }
 
     return Ok(result);
 }
 
-fn assert_sequential_ids(cards: &Vec<ScratchCard>) -> Result<(), GenericError> {
-    let mut expected_next_id: u32 = 1;
-    for card in cards {
-        if card.id != expected_next_id {
-            return Err(GenericError::new("the card ids are not ascending"));
+fn assert_sequential_ids(cards: &[ScratchCard]) -> Result<(), AocError> {
+    for (index, card) in cards.iter().enumerate() {
+        if card.id as usize != index + 1 {
+            return Err(AocError::validation(format!(
+                "the card ids are not ascending, expected card {} but found card {}",
+                index + 1,
+                card.id
+            )));
         }
-
-        expected_next_id += 1;
     }
 
     return Ok(());
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::day4::{challenge1, challenge2};
+
+    static EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_challenge2_example() {
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 30);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::string_functions::split_and_clean;
-use crate::GenericError;
+use crate::error::AocError;
+use crate::string_functions::{first_non_ascii, split_and_clean};
 
 #[derive(Debug, PartialEq)]
 pub struct ScratchCard {
@@ -10,20 +10,21 @@ pub struct ScratchCard {
 }
 
 impl ScratchCard {
-    pub fn parse_all(input: &str) -> Result<Vec<ScratchCard>, GenericError> {
+    pub fn parse_all(input: &str) -> Result<Vec<ScratchCard>, AocError> {
         let mut result: Vec<ScratchCard> = Vec::new();
 
         for line in input.lines() {
-            result.push(ScratchCard::parse(line)?);
+            result.push(ScratchCard::parse(line).map_err(|e| e.locate(input))?);
         }
 
         return Ok(result);
     }
 
-    pub fn parse(input: &str) -> Result<ScratchCard, GenericError> {
-        if !input.is_ascii() {
-            return Err(GenericError::new(
+    pub fn parse(input: &str) -> Result<ScratchCard, AocError> {
+        if let Some(non_ascii) = first_non_ascii(input) {
+            return Err(AocError::parse(
                 "the input must consist of ascii chars only",
+                non_ascii,
             ));
         }
 
@@ -44,31 +45,35 @@ impl ScratchCard {
         });
     }
 
-    fn extract_game_id(input: &str) -> Result<(u32, &str), GenericError> {
+    fn extract_game_id(input: &str) -> Result<(u32, &str), AocError> {
         let parts = split_and_clean(input, ":");
         if parts.len() != 2 {
-            return Err(GenericError::new("cannot extract game id from input"));
+            return Err(AocError::parse("cannot extract game id from input", input));
         }
 
         let sub_parts = split_and_clean(parts[0], " ");
         if sub_parts.len() != 2 {
-            return Err(GenericError::new("cannot extract game id from input"));
+            return Err(AocError::parse(
+                "cannot extract game id from input",
+                parts[0],
+            ));
         }
 
         let maybe_id = sub_parts[1].parse::<u32>();
         if maybe_id.is_err() {
-            return Err(GenericError::new("cannot extract game id from input"));
+            return Err(AocError::parse(
+                "cannot extract game id from input",
+                sub_parts[1],
+            ));
         }
 
         return Ok((maybe_id.unwrap(), parts[1]));
     }
 
-    fn extract_winning_and_player_numbers(
-        input: &str,
-    ) -> Result<(Vec<u32>, Vec<u32>), GenericError> {
+    fn extract_winning_and_player_numbers(input: &str) -> Result<(Vec<u32>, Vec<u32>), AocError> {
         let parts = split_and_clean(input, "|");
         if parts.len() != 2 {
-            return Err(GenericError::new("cannot extract number from input"));
+            return Err(AocError::parse("cannot extract number from input", input));
         }
 
         return Ok((
@@ -77,14 +82,14 @@ impl ScratchCard {
         ));
     }
 
-    fn extract_numbers(input: &str) -> Result<Vec<u32>, GenericError> {
+    fn extract_numbers(input: &str) -> Result<Vec<u32>, AocError> {
         let parts = split_and_clean(input, " ");
 
         let mut result: Vec<u32> = Vec::new();
         for part in parts {
             let maybe_number = part.parse::<u32>();
             if maybe_number.is_err() {
-                return Err(GenericError::new("cannot convert input into number"));
+                return Err(AocError::parse("cannot convert input into number", part));
             }
 
             let number = maybe_number.unwrap();
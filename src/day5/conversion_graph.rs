@@ -0,0 +1,240 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+
+use crate::day5::type_conversion::{merge_ranges, TypeConversion};
+use crate::error::AocError;
+
+/// The ranges of values of one category.
+#[derive(Debug, PartialEq)]
+pub struct Stage {
+    pub category: String,
+    pub ranges: Vec<Range<u128>>,
+}
+
+/// The conversions of an almanac, connected by their source and destination categories.
+///
+/// Conversions may appear in any order, a value is converted along the shortest chain of
+/// conversions between two categories.
+pub struct ConversionGraph {
+    conversions: Vec<TypeConversion>,
+    outgoing: HashMap<String, Vec<usize>>, // category -> indices of conversions starting there
+}
+
+impl ConversionGraph {
+    pub fn new(conversions: Vec<TypeConversion>) -> ConversionGraph {
+        let mut outgoing: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, conversion) in conversions.iter().enumerate() {
+            outgoing
+                .entry(conversion.source.clone())
+                .or_default()
+                .push(index);
+        }
+
+        return ConversionGraph {
+            conversions,
+            outgoing,
+        };
+    }
+
+    /// All categories, in the order in which they first appear.
+    pub fn categories(&self) -> Vec<&str> {
+        let mut result: Vec<&str> = Vec::new();
+        for conversion in &self.conversions {
+            for category in [&conversion.source, &conversion.destination] {
+                if !result.contains(&category.as_str()) {
+                    result.push(category);
+                }
+            }
+        }
+
+        return result;
+    }
+
+    /// The conversions that lead from the `from` category to the `to` category, in order.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&TypeConversion>, AocError> {
+        let categories = self.categories();
+        for category in [from, to] {
+            if !categories.contains(&category) {
+                return Err(AocError::validation(format!(
+                    "there is no category named {category}"
+                )));
+            }
+        }
+
+        // breadth first search, remembering the conversion that reached each category
+        let mut reached_by: HashMap<&str, Option<usize>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                break;
+            }
+
+            for index in self.outgoing.get(category).into_iter().flatten() {
+                let destination = self.conversions[*index].destination.as_str();
+                if !reached_by.contains_key(destination) {
+                    reached_by.insert(destination, Some(*index));
+                    queue.push_back(destination);
+                }
+            }
+        }
+
+        if !reached_by.contains_key(to) {
+            return Err(AocError::validation(format!(
+                "there is no chain of conversions from {from} to {to}"
+            )));
+        }
+
+        let mut result = Vec::new();
+        let mut category = to;
+        while let Some(index) = reached_by[category] {
+            let conversion = &self.conversions[index];
+            result.push(conversion);
+            category = &conversion.source;
+        }
+        result.reverse();
+
+        return Ok(result);
+    }
+
+    /// One conversion from `from` to `to`, composed of the chain of conversions between them.
+    ///
+    /// Its inverse converts `to` back to `from`.
+    pub fn collapse(&self, from: &str, to: &str) -> Result<TypeConversion, AocError> {
+        let mut result = TypeConversion::identity(from);
+        for conversion in self.path(from, to)? {
+            result = result.compose(conversion)?;
+        }
+
+        return Ok(result);
+    }
+
+    pub fn convert(&self, value: u128, from: &str, to: &str) -> Result<u128, AocError> {
+        let mut current_value = value;
+        for conversion in self.path(from, to)? {
+            current_value = conversion.convert(current_value)?;
+        }
+
+        return Ok(current_value);
+    }
+
+    /// The ranges after each conversion from `from` to `to`.
+    ///
+    /// The first stage holds the given ranges, sorted and merged.
+    pub fn convert_ranges(
+        &self,
+        ranges: Vec<Range<u128>>,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<Stage>, AocError> {
+        let path = self.path(from, to)?;
+
+        let mut stages = vec![Stage {
+            category: from.to_string(),
+            ranges: merge_ranges(ranges),
+        }];
+        for conversion in path {
+            let ranges = conversion.convert_ranges(&stages.last().unwrap().ranges)?;
+            stages.push(Stage {
+                category: conversion.destination.clone(),
+                ranges,
+            });
+        }
+
+        return Ok(stages);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day5::conversion_graph::ConversionGraph;
+    use crate::day5::type_conversion::TypeConversion;
+
+    fn graph(blocks: &[&str]) -> ConversionGraph {
+        let conversions = blocks
+            .iter()
+            .map(|block| TypeConversion::parse(&block.lines().collect::<Vec<&str>>()).unwrap())
+            .collect();
+
+        return ConversionGraph::new(conversions);
+    }
+
+    fn example() -> ConversionGraph {
+        // out of order on purpose
+        return graph(&[
+            "soil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15",
+            "seed-to-soil map:\n50 98 2\n52 50 48",
+            "fertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4",
+        ]);
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(
+            example().categories(),
+            vec!["soil", "fertilizer", "seed", "water"]
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        let sut = example();
+        let path = sut.path("seed", "water").unwrap();
+
+        assert_eq!(
+            path.iter()
+                .map(|c| c.destination.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["soil", "fertilizer", "water"]
+        );
+        assert!(sut.path("soil", "soil").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_convert() {
+        let sut = example();
+
+        assert_eq!(sut.convert(79, "seed", "water").unwrap(), 81);
+        assert_eq!(sut.convert(14, "seed", "fertilizer").unwrap(), 53);
+        assert_eq!(sut.convert(53, "soil", "water").unwrap(), 27);
+    }
+
+    #[test]
+    fn test_collapse() {
+        let sut = example();
+        let collapsed = sut.collapse("seed", "water").unwrap();
+        let inverse = collapsed.inverse().unwrap();
+
+        for seed in 0..200 {
+            let water = sut.convert(seed, "seed", "water").unwrap();
+            assert_eq!(collapsed.convert(seed).unwrap(), water);
+            assert_eq!(inverse.convert(water).unwrap(), seed);
+        }
+    }
+
+    #[test]
+    fn test_broken_chain() {
+        let sut = example();
+
+        assert!(sut.path("water", "seed").is_err());
+        assert!(sut.path("seed", "location").is_err());
+    }
+
+    #[test]
+    fn test_convert_ranges() {
+        let stages = example()
+            .convert_ranges(vec![79..93, 55..68], "seed", "fertilizer")
+            .unwrap();
+
+        assert_eq!(
+            stages
+                .iter()
+                .map(|s| (s.category.as_str(), s.ranges.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("seed", vec![55..68, 79..93]),
+                ("soil", vec![57..70, 81..95]),
+                ("fertilizer", vec![57..70, 81..95]),
+            ]
+        );
+    }
+}
@@ -1,108 +1,175 @@
 use std::cmp::min;
-use std::error::Error;
-use std::fs;
+use std::collections::HashSet;
 use std::ops::Range;
 
+use crate::day5::conversion_graph::{ConversionGraph, Stage};
 use crate::day5::type_conversion::TypeConversion;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 use crate::string_functions::{split_and_clean, to_u128};
-use crate::GenericError;
 
-mod type_conversion;
+pub mod conversion_graph;
+pub mod type_conversion;
 
-pub fn day5_challenge1(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let lines = text.lines().collect::<Vec<&str>>();
+pub struct Day5;
 
-    if lines.len() < 3 {
-        return Err(Box::new(GenericError::new("unexpected puzzle input")));
-    }
+pub struct Almanac {
+    pub seed_numbers: Vec<u128>,
+    pub conversions: ConversionGraph,
+}
 
-    let seed_ids = extract_seed_ids_challenge1(lines[0])?;
-    let raw_mappings = extract_raw_mappings(&lines[2..])?;
-    let mut conversions: Vec<TypeConversion> = Vec::new();
-    for raw_mapping in raw_mappings {
-        conversions.push(TypeConversion::parse(raw_mapping)?);
+impl Solver for Day5 {
+    type Input = Almanac;
+
+    fn parse(&self, input: &str) -> Result<Almanac, AocError> {
+        return parse_almanac(input);
     }
 
-    let mut min_location_id = u128::MAX;
-    for seed_id in seed_ids {
-        let maybe_location_id = apply_conversions(seed_id, &conversions);
-        if maybe_location_id.is_err() {
-            return Err(Box::new(GenericError::new(
-                "unable to transform seed to location",
-            )));
-        }
+    fn part1(&self, almanac: &Almanac) -> Result<Answer, AocError> {
+        return Ok(Answer::from(lowest_location_of_seeds(almanac)?));
+    }
 
-        min_location_id = min(min_location_id, maybe_location_id.unwrap());
+    fn part2(&self, almanac: &Almanac) -> Result<Answer, AocError> {
+        return Ok(Answer::from(lowest_location_of_seed_ranges(almanac)?));
     }
+}
 
-    return Ok(min_location_id);
+pub fn day5_challenge1(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge1);
 }
 
-pub fn day5_challenge2(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let lines = text.lines().collect::<Vec<&str>>();
+pub fn day5_challenge2(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge2);
+}
 
-    if lines.len() < 3 {
-        return Err(Box::new(GenericError::new("unexpected puzzle input")));
+pub fn challenge1(input: &str) -> Result<u128, AocError> {
+    let almanac = parse_almanac(input)?;
+    return lowest_location_of_seeds(&almanac);
+}
+
+pub fn challenge2(input: &str) -> Result<u128, AocError> {
+    let almanac = parse_almanac(input)?;
+    return lowest_location_of_seed_ranges(&almanac);
+}
+
+/// Parses the almanac, reporting every problem `validate` finds at once.
+fn parse_almanac(text: &str) -> Result<Almanac, AocError> {
+    let errors = validate(text);
+    if !errors.is_empty() {
+        return Err(AocError::several(errors));
     }
 
-    let seed_ranges = extract_seed_ids_challenge2(lines[0])?;
-    let raw_mappings = extract_raw_mappings(&lines[2..])?;
+    let lines = text.lines().collect::<Vec<&str>>();
+    let seed_numbers = extract_seed_ids_challenge1(lines[0])?;
     let mut conversions: Vec<TypeConversion> = Vec::new();
-    for raw_mapping in raw_mappings {
+    for raw_mapping in extract_raw_mappings(&lines[2..]) {
         conversions.push(TypeConversion::parse(raw_mapping)?);
     }
 
-    let mut min_location_id = u128::MAX;
-    for seed_range in seed_ranges {
-        for seed_id in seed_range {
-            let maybe_location_id = apply_conversions(seed_id, &conversions);
-            if maybe_location_id.is_err() {
-                return Err(Box::new(GenericError::new(
-                    "unable to transform seed to location",
-                )));
-            }
+    return Ok(Almanac {
+        seed_numbers,
+        conversions: ConversionGraph::new(conversions),
+    });
+}
+
+/// Finds all problems of an almanac, located in the almanac: an unparsable seed line, invalid
+/// or duplicate map headers and invalid range mappings.
+pub fn validate(text: &str) -> Vec<AocError> {
+    let lines = text.lines().collect::<Vec<&str>>();
+    if lines.len() < 3 {
+        return vec![AocError::parse("unexpected puzzle input", text).locate(text)];
+    }
 
-            min_location_id = min(min_location_id, maybe_location_id.unwrap());
+    let mut errors = Vec::new();
+    if let Err(error) = extract_seed_ids_challenge1(lines[0]) {
+        errors.push(error);
+    }
+
+    let mut seen = HashSet::new();
+    for raw_mapping in extract_raw_mappings(&lines[2..]) {
+        match TypeConversion::parse_header(raw_mapping) {
+            Ok(categories) => {
+                if !seen.insert(categories) {
+                    errors.push(AocError::parse("duplicate map", raw_mapping[0]));
+                }
+            }
+            Err(error) => errors.push(error),
         }
+
+        errors.extend(type_conversion::validate(
+            raw_mapping.get(1..).unwrap_or_default(),
+        ));
+    }
+
+    return errors.into_iter().map(|error| error.locate(text)).collect();
+}
+
+fn lowest_location_of_seeds(almanac: &Almanac) -> Result<u128, AocError> {
+    let mut min_location_id = u128::MAX;
+    for seed_id in &almanac.seed_numbers {
+        let location_id = almanac.conversions.convert(*seed_id, "seed", "location")?;
+        min_location_id = min(min_location_id, location_id);
     }
 
     return Ok(min_location_id);
 }
 
-fn extract_seed_ids_challenge1(input: &str) -> Result<Vec<u128>, GenericError> {
+fn lowest_location_of_seed_ranges(almanac: &Almanac) -> Result<u128, AocError> {
+    let stages = seed_range_stages(almanac)?;
+    let locations = stages.last().expect("the seed ranges are the first stage");
+
+    return locations
+        .ranges
+        .first()
+        .map(|r| r.start)
+        .ok_or_else(|| AocError::validation("there are no seeds"));
+}
+
+/// The sorted and merged ranges of each category from seed to location, starting with the seeds.
+pub fn seed_range_stages(almanac: &Almanac) -> Result<Vec<Stage>, AocError> {
+    let seed_ranges = extract_seed_ids_challenge2(&almanac.seed_numbers)?;
+    return almanac
+        .conversions
+        .convert_ranges(seed_ranges, "seed", "location");
+}
+
+fn extract_seed_ids_challenge1(input: &str) -> Result<Vec<u128>, AocError> {
     let parts = split_and_clean(input, " ");
     if parts.len() < 2 {
-        return Err(GenericError::new("unable to extract seed ids"));
+        return Err(AocError::parse("unable to extract seed ids", input));
     }
 
     let mut result: Vec<u128> = Vec::new();
-    for part_index in 1..parts.len() {
-        result.push(to_u128(parts[part_index])?);
+    for part in &parts[1..] {
+        result.push(to_u128(part)?);
     }
 
     return Ok(result);
 }
 
-fn extract_seed_ids_challenge2(input: &str) -> Result<Vec<Range<u128>>, GenericError> {
-    let numbers = extract_seed_ids_challenge1(input)?;
-    if numbers.len() % 2 != 0 {
-        return Err(GenericError::new("uneven amount of seed numbers"));
+fn extract_seed_ids_challenge2(numbers: &[u128]) -> Result<Vec<Range<u128>>, AocError> {
+    if !numbers.len().is_multiple_of(2) {
+        return Err(AocError::validation("uneven amount of seed numbers"));
     }
 
     let mut result: Vec<Range<u128>> = Vec::new();
     for index in (0..numbers.len()).step_by(2) {
         let start = numbers[index];
         let length = numbers[index + 1];
+        let end = start.checked_add(length).ok_or_else(|| {
+            AocError::validation(format!(
+                "the seed range starting at {start} exceeds the largest 128 bit number"
+            ))
+        })?;
 
-        result.push(start..start + length);
+        result.push(start..end);
     }
 
     return Ok(result);
 }
 
-fn extract_raw_mappings<'a, 'b>(input: &'a [&'b str]) -> Result<Vec<&'a [&'b str]>, GenericError> {
+fn extract_raw_mappings<'a, 'b>(input: &'a [&'b str]) -> Vec<&'a [&'b str]> {
     let mut current_slice_start: usize = 0;
     let mut result: Vec<&'a [&'b str]> = Vec::new();
 
@@ -118,7 +185,10 @@ fn extract_raw_mappings<'a, 'b>(input: &'a [&'b str]) -> Result<Vec<&'a [&'b str
             current_slice_end += 1;
         }
 
-        result.push(&input[current_slice_start..current_slice_end]);
+        if current_slice_start < current_slice_end {
+            // trailing empty lines leave nothing to collect
+            result.push(&input[current_slice_start..current_slice_end]);
+        }
         current_slice_start = current_slice_end;
     }
 
@@ -126,24 +196,163 @@ fn extract_raw_mappings<'a, 'b>(input: &'a [&'b str]) -> Result<Vec<&'a [&'b str
         result.push(&input[current_slice_start..]);
     }
 
-    return Ok(result);
+    return result;
 }
 
-fn apply_conversions(
-    source_id: u128,
-    conversions: &Vec<TypeConversion>,
-) -> Result<u128, GenericError> {
-    let mut current_value = source_id;
-    for conversion in conversions {
-        current_value = conversion.convert(current_value)?;
+#[cfg(test)]
+mod tests {
+    use crate::day5::{
+        challenge1, challenge2, extract_raw_mappings, parse_almanac, seed_range_stages, validate,
+    };
+    use crate::error::AocError;
+
+    static EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 35);
     }
 
-    return Ok(current_value);
-}
+    #[test]
+    fn test_challenge2_example() {
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 46);
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::day5::extract_raw_mappings;
+    #[test]
+    fn test_seed_range_past_u128() {
+        let input = EXAMPLE.replace(
+            "seeds: 79",
+            "seeds: 340282366920938463463374607431768211455",
+        );
+
+        assert!(challenge1(&input).is_ok());
+        assert!(matches!(challenge2(&input), Err(AocError::Validation(_))));
+    }
+
+    #[test]
+    fn test_seed_range_stages() {
+        let almanac = parse_almanac(EXAMPLE).unwrap();
+        let stages = seed_range_stages(&almanac).unwrap();
+
+        assert_eq!(stages.len(), 8);
+        assert_eq!(stages[0].category, "seed");
+        assert_eq!(stages[0].ranges, vec![55..68, 79..93]);
+        assert_eq!(stages[1].category, "soil");
+        assert_eq!(stages[1].ranges, vec![57..70, 81..95]);
+        assert_eq!(stages[7].category, "location");
+        assert_eq!(stages[7].ranges.first().unwrap().start, 46);
+    }
+
+    #[test]
+    fn test_out_of_order_maps() {
+        let blocks = EXAMPLE.split("\n\n").collect::<Vec<&str>>();
+        let mut shuffled = vec![blocks[0]];
+        shuffled.extend(blocks[1..].iter().rev());
+
+        let input = shuffled.join("\n\n");
+
+        assert_eq!(challenge1(&input).unwrap(), 35);
+        assert_eq!(challenge2(&input).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_broken_chain() {
+        let input = EXAMPLE.replace("water-to-light", "water-to-lamp");
+
+        assert!(matches!(challenge1(&input), Err(AocError::Validation(_))));
+    }
+
+    #[test]
+    fn test_duplicate_map() {
+        let input = EXAMPLE.replace("water-to-light", "seed-to-soil");
+
+        match challenge1(&input) {
+            Err(AocError::Parse(error)) => assert_eq!(error.line, Some(18)),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = EXAMPLE
+            .replace("37 52 2", "37 52 0")
+            .replace("88 18 7", "88 18 8");
+        let errors = validate(&input);
+
+        assert_eq!(errors.len(), 2);
+        for (error, line) in errors.iter().zip([9, 20]) {
+            match error {
+                AocError::Parse(error) => assert_eq!(error.line, Some(line)),
+                _ => panic!("expected a parse error"),
+            }
+        }
+
+        assert!(matches!(challenge1(&input), Err(AocError::Several(errors)) if errors.len() == 2));
+        assert!(validate(EXAMPLE).is_empty());
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let input = format!("{EXAMPLE}\n\n\n");
+
+        assert!(validate(&input).is_empty());
+        assert_eq!(challenge1(&input).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_parse_reports_every_problem() {
+        let input = EXAMPLE
+            .replace("seeds: 79", "seeds: 7x9")
+            .replace("water-to-light map:", "water-light map:")
+            .replace("0 69 1", "0 68 2")
+            .replace("light-to-temperature", "seed-to-soil");
+
+        let lines = match challenge1(&input) {
+            Err(AocError::Several(errors)) => errors
+                .iter()
+                .map(|error| match error {
+                    AocError::Parse(error) => error.line.unwrap(),
+                    _ => panic!("expected a parse error"),
+                })
+                .collect::<Vec<usize>>(),
+            _ => panic!("expected several errors"),
+        };
+
+        assert_eq!(lines, vec![1, 18, 22, 28]);
+    }
 
     #[test]
     fn test_extract_raw_mappings() {
@@ -166,7 +375,7 @@ fertilizer-to-water map:
 57 7 4"
             .lines()
             .collect::<Vec<&str>>();
-        let actual = extract_raw_mappings(&input[2..]).unwrap();
+        let actual = extract_raw_mappings(&input[2..]);
 
         assert_eq!(
             actual,
@@ -1,10 +1,14 @@
-use std::cmp::Ordering;
 use std::cmp::Ordering::Equal;
+use std::cmp::{min, Ordering};
+use std::ops::Range;
 
+use crate::error::AocError;
 use crate::string_functions::{split_and_clean, to_u128};
-use crate::GenericError;
 
+/// Converts the values of the `source` category to the `destination` category.
 pub struct TypeConversion {
+    pub source: String,
+    pub destination: String,
     mappings: Vec<RangeMapping>,
 }
 
@@ -16,52 +20,183 @@ struct RangeMapping {
 }
 
 impl TypeConversion {
-    pub fn parse(input_lines: &[&str]) -> Result<TypeConversion, GenericError> {
+    pub fn parse(input_lines: &[&str]) -> Result<TypeConversion, AocError> {
+        let (source, destination) = TypeConversion::parse_header(input_lines)?;
+        let range_mappings = TypeConversion::parse_mappings(&input_lines[1..])?;
+
+        return Ok(TypeConversion {
+            source,
+            destination,
+            mappings: range_mappings,
+        });
+    }
+
+    /// The source and destination category of the map, which must have at least one mapping.
+    pub fn parse_header(input_lines: &[&str]) -> Result<(String, String), AocError> {
         if input_lines.len() < 2 {
-            return Err(GenericError::new("cannot extract type conversion"));
+            return Err(AocError::parse(
+                "cannot extract type conversion",
+                input_lines.first().copied().unwrap_or_default(),
+            ));
         }
 
         let mapping_parts = split_and_clean(input_lines[0], " ");
-        if mapping_parts.len() != 2 {
-            return Err(GenericError::new(
+        if mapping_parts.len() != 2 || mapping_parts[1] != "map:" {
+            return Err(AocError::parse(
                 "cannot extract source and destination type",
+                input_lines[0],
             ));
         }
 
         let sub_parts = split_and_clean(mapping_parts[0], "-");
-        if sub_parts.len() != 3 {
-            return Err(GenericError::new(
+        if sub_parts.len() != 3 || sub_parts[1] != "to" {
+            return Err(AocError::parse(
                 "cannot extract source and destination type",
+                mapping_parts[0],
             ));
         }
 
-        let range_mappings = TypeConversion::parse_mappings(&input_lines[1..])?;
+        return Ok((sub_parts[0].to_string(), sub_parts[2].to_string()));
+    }
+
+    /// The conversion that leaves every value of `category` unchanged.
+    pub fn identity(category: &str) -> TypeConversion {
+        return TypeConversion {
+            source: category.to_string(),
+            destination: category.to_string(),
+            mappings: vec![RangeMapping {
+                source_offset: 0,
+                destination_offset: 0,
+                length: None,
+            }],
+        };
+    }
+
+    /// The conversion from the destination back to the source category.
+    ///
+    /// Only conversions that map every value to exactly one value can be inverted.
+    pub fn inverse(&self) -> Result<TypeConversion, AocError> {
+        let mut mappings = self
+            .mappings
+            .iter()
+            .filter(|m| m.length != Some(0))
+            .map(|m| RangeMapping {
+                source_offset: m.destination_offset,
+                destination_offset: m.source_offset,
+                length: m.length,
+            })
+            .collect::<Vec<RangeMapping>>();
+        mappings.sort_by_key(|m| m.source_offset);
+
+        if !TypeConversion::covers_every_value_once(&mappings) {
+            return Err(AocError::validation(format!(
+                "the {}-to-{} map cannot be inverted, its destination ranges overlap or leave gaps",
+                self.source, self.destination
+            )));
+        }
 
         return Ok(TypeConversion {
-            mappings: range_mappings,
+            source: self.destination.clone(),
+            destination: self.source.clone(),
+            mappings,
+        });
+    }
+
+    // expects the mappings to be sorted by their source offset
+    fn covers_every_value_once(mappings: &[RangeMapping]) -> bool {
+        let mut next_source_index: u128 = 0;
+        for (index, mapping) in mappings.iter().enumerate() {
+            if mapping.source_offset != next_source_index {
+                return false;
+            }
+
+            match mapping
+                .length
+                .and_then(|l| mapping.source_offset.checked_add(l))
+            {
+                Some(end) => next_source_index = end,
+                None => return mapping.length.is_none() && index + 1 == mappings.len(),
+            }
+        }
+
+        return false;
+    }
+
+    /// Merges this conversion and a conversion of its destination category into one
+    /// conversion with a single flat list of range mappings.
+    pub fn compose(&self, next: &TypeConversion) -> Result<TypeConversion, AocError> {
+        if self.destination != next.source {
+            return Err(AocError::validation(format!(
+                "cannot convert {} to {} after converting to {}",
+                next.source, next.destination, self.destination
+            )));
+        }
+
+        let mut mappings: Vec<RangeMapping> = Vec::new();
+        for mapping in &self.mappings {
+            let end = mapping.length.map(|l| mapping.destination_offset + l);
+            let mut start = mapping.destination_offset;
+            while end.is_none_or(|end| start < end) {
+                // the part of the mapping that ends up in a single mapping of the next conversion
+                let next_mapping = next.find_mapping(start)?;
+                let next_end = next_mapping.length.map(|l| next_mapping.source_offset + l);
+                let piece_end = match (end, next_end) {
+                    (Some(end), Some(next_end)) => Some(min(end, next_end)),
+                    (end, None) => end,
+                    (None, next_end) => next_end,
+                };
+
+                push_merged(
+                    &mut mappings,
+                    RangeMapping {
+                        source_offset: mapping.source_offset + (start - mapping.destination_offset),
+                        destination_offset: next_mapping.destination_offset
+                            + (start - next_mapping.source_offset),
+                        length: piece_end.map(|piece_end| piece_end - start),
+                    },
+                );
+
+                match piece_end {
+                    Some(piece_end) => start = piece_end,
+                    None => break,
+                }
+            }
+        }
+
+        return Ok(TypeConversion {
+            source: self.source.clone(),
+            destination: next.destination.clone(),
+            mappings,
         });
     }
 
-    fn parse_mappings(input: &[&str]) -> Result<Vec<RangeMapping>, GenericError> {
+    fn parse_mappings(input: &[&str]) -> Result<Vec<RangeMapping>, AocError> {
         let mut range_mappings: Vec<RangeMapping> = Vec::new();
 
         for line in input {
             range_mappings.push(RangeMapping::parse(line)?);
         }
 
-        range_mappings.sort_by(|r1, r2| r1.source_offset.cmp(&r2.source_offset));
+        let lines = input.iter().copied().zip(range_mappings.iter().cloned());
+        if let Some(error) = validate_mappings(lines.collect()).into_iter().next() {
+            return Err(error);
+        }
+
+        range_mappings.sort_by_key(|r| r.source_offset);
         TypeConversion::add_missing_range_mappings(&mut range_mappings)?;
 
         return Ok(range_mappings);
     }
 
-    fn add_missing_range_mappings(mappings: &mut Vec<RangeMapping>) -> Result<(), GenericError> {
+    fn add_missing_range_mappings(mappings: &mut Vec<RangeMapping>) -> Result<(), AocError> {
         let mut next_source_index: u128 = 0;
         let mut index: usize = 0;
         while index < mappings.len() {
             let mapping = mappings[index].clone();
             if mapping.length.is_none() {
-                return Err(GenericError::new("found unexpected unbound range mapping"));
+                return Err(AocError::validation(
+                    "found unexpected unbound range mapping",
+                ));
             }
 
             if mapping.source_offset > next_source_index {
@@ -92,13 +227,40 @@ impl TypeConversion {
         return Ok(());
     }
 
-    pub fn convert(&self, source_value: u128) -> Result<u128, GenericError> {
+    pub fn convert(&self, source_value: u128) -> Result<u128, AocError> {
         let mapping = self.find_mapping(source_value)?;
         let source_distance = source_value as i128 - mapping.source_offset as i128;
         return Ok((mapping.destination_offset as i128 + source_distance) as u128);
     }
 
-    fn find_mapping(&self, source_value: u128) -> Result<&RangeMapping, GenericError> {
+    /// Converts whole ranges, splitting them wherever they span several range mappings.
+    ///
+    /// The resulting ranges are sorted, and overlapping or adjacent ranges are merged.
+    pub fn convert_ranges(
+        &self,
+        source_ranges: &[Range<u128>],
+    ) -> Result<Vec<Range<u128>>, AocError> {
+        let mut result = Vec::new();
+        for source_range in source_ranges {
+            let mut start = source_range.start;
+            while start < source_range.end {
+                let mapping = self.find_mapping(start)?;
+                let end = match mapping.length {
+                    Some(length) => min(source_range.end, mapping.source_offset + length),
+                    None => source_range.end,
+                };
+
+                let destination_start =
+                    mapping.destination_offset + (start - mapping.source_offset);
+                result.push(destination_start..destination_start + (end - start));
+                start = end;
+            }
+        }
+
+        return Ok(merge_ranges(result));
+    }
+
+    fn find_mapping(&self, source_value: u128) -> Result<&RangeMapping, AocError> {
         if self.mappings.len() < 128 {
             return self.find_mapping_linear(source_value);
         }
@@ -106,40 +268,132 @@ impl TypeConversion {
         return self.find_mapping_binary(source_value);
     }
 
-    fn find_mapping_linear(&self, source_value: u128) -> Result<&RangeMapping, GenericError> {
+    fn find_mapping_linear(&self, source_value: u128) -> Result<&RangeMapping, AocError> {
         for mapping in &self.mappings {
             if mapping.find(source_value) == Equal {
                 return Ok(mapping);
             }
         }
 
-        return Err(GenericError::new("unable to find range mapping"));
+        return Err(AocError::validation("unable to find range mapping"));
     }
 
-    fn find_mapping_binary(&self, source_value: u128) -> Result<&RangeMapping, GenericError> {
+    // expects the mappings to be sorted by their source offset and not to overlap
+    fn find_mapping_binary(&self, source_value: u128) -> Result<&RangeMapping, AocError> {
+        // the mapping can only be in `lower..upper`
         let mut lower = 0;
         let mut upper = self.mappings.len();
 
-        while upper >= lower {
+        while lower < upper {
             let index = lower + ((upper - lower) / 2);
             let result = self.mappings[index].find(source_value);
 
             match result {
                 Equal => return Ok(&self.mappings[index]),
                 Ordering::Less => upper = index,
-                Ordering::Greater => lower = index,
+                Ordering::Greater => lower = index + 1,
             };
         }
 
-        return Err(GenericError::new("unable to find range mapping"));
+        return Err(AocError::validation("unable to find range mapping"));
+    }
+}
+
+/// Finds all unparsable, empty, overflowing and overlapping range mappings in the lines of
+/// a map, without its header.
+///
+/// The errors point to the offending lines, so that they can be located in the almanac.
+pub fn validate(input_lines: &[&str]) -> Vec<AocError> {
+    let mut errors = Vec::new();
+    let mut lines = Vec::new();
+    for line in input_lines {
+        match RangeMapping::parse(line) {
+            Ok(mapping) => lines.push((*line, mapping)),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    errors.extend(validate_mappings(lines));
+    return errors;
+}
+
+fn validate_mappings(mut lines: Vec<(&str, RangeMapping)>) -> Vec<AocError> {
+    let mut errors = Vec::new();
+    lines.sort_by_key(|(_, mapping)| mapping.source_offset);
+
+    // the end of the source ranges seen so far, with the line of the range that reaches furthest
+    let mut furthest: Option<(u128, &str)> = None;
+    for (line, mapping) in lines {
+        let length = mapping.length.unwrap_or_default();
+        if length == 0 {
+            errors.push(AocError::parse("the range mapping is empty", line));
+            continue;
+        }
+
+        let source_end = mapping.source_offset.checked_add(length);
+        let destination_end = mapping.destination_offset.checked_add(length);
+        let Some((source_end, _)) = source_end.zip(destination_end) else {
+            errors.push(AocError::parse(
+                "the range mapping exceeds the largest 128 bit number",
+                line,
+            ));
+            continue;
+        };
+
+        match furthest {
+            Some((end, other_line)) if mapping.source_offset < end => {
+                errors.push(AocError::parse(
+                    format!("the source range overlaps the one of '{other_line}'"),
+                    line,
+                ));
+                if source_end > end {
+                    furthest = Some((source_end, line));
+                }
+            }
+            _ => furthest = Some((source_end, line)),
+        }
+    }
+
+    return errors;
+}
+
+// appends the mapping, extending the last mapping instead if it continues it seamlessly
+fn push_merged(mappings: &mut Vec<RangeMapping>, mapping: RangeMapping) {
+    if let Some(last) = mappings.last_mut() {
+        if let Some(length) = last.length {
+            if last.source_offset + length == mapping.source_offset
+                && last.destination_offset + length == mapping.destination_offset
+            {
+                last.length = mapping.length.map(|l| length + l);
+                return;
+            }
+        }
     }
+
+    mappings.push(mapping);
+}
+
+/// Sorts the ranges and merges those that overlap or touch, dropping empty ranges.
+pub fn merge_ranges(mut ranges: Vec<Range<u128>>) -> Vec<Range<u128>> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| r.start);
+
+    let mut result: Vec<Range<u128>> = Vec::new();
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => result.push(range),
+        }
+    }
+
+    return result;
 }
 
 impl RangeMapping {
-    fn parse(input: &str) -> Result<RangeMapping, GenericError> {
+    fn parse(input: &str) -> Result<RangeMapping, AocError> {
         let parts = split_and_clean(input, " ");
         if parts.len() != 3 {
-            return Err(GenericError::new("unexpected range mapping input"));
+            return Err(AocError::parse("unexpected range mapping input", input));
         }
 
         let destination_offset = to_u128(parts[0])?;
@@ -174,7 +428,9 @@ impl RangeMapping {
 mod tests {
     use std::cmp::Ordering;
 
-    use crate::day5::type_conversion::{RangeMapping, TypeConversion};
+    use proptest::prelude::*;
+
+    use crate::day5::type_conversion::{merge_ranges, validate, RangeMapping, TypeConversion};
 
     #[test]
     fn test_parse() {
@@ -187,6 +443,8 @@ soil-to-fertilizer map:
             .collect::<Vec<&str>>();
         let sut = TypeConversion::parse(&input).unwrap();
 
+        assert_eq!(sut.source, "soil");
+        assert_eq!(sut.destination, "fertilizer");
         assert_eq!(
             sut.mappings,
             vec![
@@ -214,6 +472,49 @@ soil-to-fertilizer map:
         );
     }
 
+    #[test]
+    fn test_parse_invalid_header() {
+        for header in [
+            "soil-fertilizer map:",
+            "soil-from-fertilizer map:",
+            "soil-to-fertilizer",
+        ] {
+            assert!(TypeConversion::parse(&[header, "0 15 37"]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = [
+            "50 98 2",
+            "52 50 48",
+            "0 60 5",
+            "1 2 0",
+            "0 340282366920938463463374607431768211455 2",
+            "1 2",
+        ];
+        let messages = validate(&input)
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            messages,
+            vec![
+                "unexpected range mapping input: '1 2'",
+                "the range mapping is empty: '1 2 0'",
+                "the source range overlaps the one of '52 50 48': '0 60 5'",
+                "the range mapping exceeds the largest 128 bit number: '0 340282366920938463463374607431768211455 2'",
+            ]
+        );
+        assert!(validate(&input[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_overlapping_mappings() {
+        assert!(TypeConversion::parse(&["a-to-b map:", "0 10 10", "50 15 2"]).is_err());
+    }
+
     #[test]
     fn test_add_missing_range_mappings() {
         let mut sut = vec![
@@ -291,7 +592,7 @@ soil-to-fertilizer map:
         for value in 0..15 {
             assert_eq!(
                 sut.convert(value).unwrap(),
-                ((39i128 - 0i128) + value as i128) as u128
+                (39i128 + value as i128) as u128
             );
         }
 
@@ -314,6 +615,87 @@ soil-to-fertilizer map:
         }
     }
 
+    #[test]
+    fn test_convert_ranges() {
+        let input = "\
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15"
+            .lines()
+            .collect::<Vec<&str>>();
+        let sut = TypeConversion::parse(&input).unwrap();
+
+        // 10..20 is split into 49..54 and 0..5, 50..60 into 35..37, 37..39 and 54..60
+        let actual = sut.convert_ranges(&[10..20, 50..60]).unwrap();
+
+        assert_eq!(actual, vec![0..5, 35..39, 49..60]);
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let actual = merge_ranges(vec![10..20, 3..3, 0..5, 15..25, 5..7, 30..31]);
+
+        assert_eq!(actual, vec![0..7, 10..25, 30..31]);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let input = "\
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15"
+            .lines()
+            .collect::<Vec<&str>>();
+        let sut = TypeConversion::parse(&input).unwrap();
+        let inverse = sut.inverse().unwrap();
+
+        assert_eq!(inverse.source, "fertilizer");
+        assert_eq!(inverse.destination, "soil");
+        for value in 0..100 {
+            assert_eq!(inverse.convert(sut.convert(value).unwrap()).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_inverse_of_overlapping_destinations() {
+        // 0..10 and 20..30 both end up in 20..30
+        let sut = TypeConversion::parse(&["a-to-b map:", "20 0 10"]).unwrap();
+
+        assert!(sut.inverse().is_err());
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = TypeConversion::parse(&["seed-to-soil map:", "50 98 2", "52 50 48"]).unwrap();
+        let second =
+            TypeConversion::parse(&["soil-to-fertilizer map:", "0 15 37", "37 52 2", "39 0 15"])
+                .unwrap();
+        let sut = first.compose(&second).unwrap();
+
+        assert_eq!(sut.source, "seed");
+        assert_eq!(sut.destination, "fertilizer");
+        for value in 0..200 {
+            assert_eq!(
+                sut.convert(value).unwrap(),
+                second.convert(first.convert(value).unwrap()).unwrap()
+            );
+        }
+
+        // 0..15, 15..50, 50..52, 52..98, 98..100 and the rest
+        assert_eq!(sut.mappings.len(), 6);
+        assert!(second.compose(&first).is_err());
+    }
+
+    #[test]
+    fn test_compose_with_identity() {
+        let sut = TypeConversion::parse(&["a-to-b map:", "20 0 10", "0 20 10"]).unwrap();
+        let actual = TypeConversion::identity("a").compose(&sut).unwrap();
+
+        assert_eq!(actual.mappings, sut.mappings);
+    }
+
     #[test]
     fn test_find_mapping_linear() {
         let input = "\
@@ -394,6 +776,69 @@ soil-to-fertilizer map:
         }
     }
 
+    #[test]
+    fn test_find_mapping_binary_with_many_mappings() {
+        let ranges = (0..200)
+            .map(|i| (i % 3, 1 + i % 7, 1000 - i))
+            .collect::<Vec<_>>();
+        let sut = conversion_from_ranges(&ranges, true);
+
+        assert!(sut.mappings.len() >= 128);
+        for source_value in 0..2000 {
+            let mapping = sut.find_mapping_binary(source_value).unwrap();
+            assert_eq!(mapping.find(source_value), Ordering::Equal);
+        }
+    }
+
+    // builds a conversion from (gap to the previous range, length, destination offset) tuples,
+    // `complete` fills the gaps the way parsing does
+    fn conversion_from_ranges(ranges: &[(u128, u128, u128)], complete: bool) -> TypeConversion {
+        let mut mappings = Vec::new();
+        let mut next_source_offset = 0;
+        for (gap, length, destination_offset) in ranges {
+            mappings.push(RangeMapping {
+                source_offset: next_source_offset + gap,
+                destination_offset: *destination_offset,
+                length: Some(*length),
+            });
+            next_source_offset += gap + length;
+        }
+        if complete {
+            TypeConversion::add_missing_range_mappings(&mut mappings).unwrap();
+        }
+
+        return TypeConversion {
+            source: String::from("a"),
+            destination: String::from("b"),
+            mappings,
+        };
+    }
+
+    proptest! {
+        #[test]
+        fn test_linear_and_binary_lookup_agree(
+            ranges in prop::collection::vec((0u128..20, 1u128..20, 0u128..1000), 0..300),
+            values in prop::collection::vec(0u128..15_000, 1..50),
+            complete in any::<bool>(),
+        ) {
+            let sut = conversion_from_ranges(&ranges, complete);
+            let boundaries = sut
+                .mappings
+                .iter()
+                .flat_map(|m| [m.source_offset.saturating_sub(1), m.source_offset]);
+
+            for value in values.iter().copied().chain(boundaries) {
+                let linear = sut.find_mapping_linear(value).ok();
+                let binary = sut.find_mapping_binary(value).ok();
+
+                prop_assert_eq!(linear, binary);
+                if let Some(mapping) = binary {
+                    prop_assert_eq!(mapping.find(value), Ordering::Equal);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_parse_range_mapping() {
         let input = "50 98 2";
@@ -1,18 +1,61 @@
-use std::error::Error;
-use std::fs;
-
 use crate::day6::race::Race;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod race;
 
-pub fn day6_challenge1(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let races = Race::parse_all(&text)?;
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<Race>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Race>, AocError> {
+        return Race::parse_all(input);
+    }
+
+    fn part1(&self, races: &Vec<Race>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(product_of_ways_to_win(races)));
+    }
+}
 
+pub fn day6_challenge1(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn challenge1(input: &str) -> Result<u128, AocError> {
+    let races = Race::parse_all(input)?;
+    return Ok(product_of_ways_to_win(&races));
+}
+
+fn product_of_ways_to_win(races: &[Race]) -> u128 {
     let mut result: u128 = 1;
     for race in races {
         result *= race.number_of_ways_to_win();
     }
 
-    return Ok(result);
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day6::challenge1;
+
+    #[test]
+    fn test_challenge1_example() {
+        let input = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
+        assert_eq!(challenge1(input).unwrap(), 288);
+    }
+
+    #[test]
+    fn test_challenge1_single_race_example() {
+        let input = "\
+Time:      71530
+Distance:  940200";
+
+        assert_eq!(challenge1(input).unwrap(), 71503);
+    }
 }
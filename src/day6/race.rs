@@ -1,5 +1,5 @@
+use crate::error::AocError;
 use crate::string_functions::{all_to_u128, split_and_clean};
-use crate::GenericError;
 
 #[derive(Debug, PartialEq)]
 pub struct Race {
@@ -9,21 +9,30 @@ pub struct Race {
 }
 
 impl Race {
-    pub fn parse_all(input: &str) -> Result<Vec<Race>, GenericError> {
+    pub fn parse_all(input: &str) -> Result<Vec<Race>, AocError> {
         let lines = input.lines().collect::<Vec<&str>>();
         if lines.len() != 2 {
-            return Err(GenericError::new("unable to parse races"));
+            return Err(
+                AocError::parse("expected exactly two lines of races", input).locate(input),
+            );
         }
 
         let raw_times = split_and_clean(lines[0], " ");
         let raw_distances = split_and_clean(lines[1], " ");
 
-        if raw_times.len() < 2 || raw_distances.len() != raw_times.len() {
-            return Err(GenericError::new("unable to parse races"));
+        if raw_times.len() < 2 {
+            return Err(AocError::parse("no race times found", lines[0]).locate(input));
         }
 
-        let times = all_to_u128(&raw_times[1..])?;
-        let distances = all_to_u128(&raw_distances[1..])?;
+        if raw_distances.len() != raw_times.len() {
+            return Err(
+                AocError::parse("number of distances does not match the times", lines[1])
+                    .locate(input),
+            );
+        }
+
+        let times = all_to_u128(&raw_times[1..]).map_err(|e| e.locate(input))?;
+        let distances = all_to_u128(&raw_distances[1..]).map_err(|e| e.locate(input))?;
 
         let races = (0..times.len())
             .map(|i| Race::new(times[i], distances[i]))
@@ -43,17 +52,26 @@ impl Race {
     }
 
     pub fn number_of_ways_to_win(&self) -> u128 {
-        self.maximum_charge_time() - self.minimum_charge_time() + 1
+        // without a positive discriminant (or with a NaN one), the record can at best be tied
+        if self._sqrt.is_nan() || self._sqrt <= 0.0 {
+            return 0;
+        }
+
+        return self
+            .maximum_charge_time()
+            .checked_sub(self.minimum_charge_time())
+            .map_or(0, |difference| difference + 1);
     }
 
+    // the record has to be beaten, so a charge time that exactly matches it does not count
     fn minimum_charge_time(&self) -> u128 {
         let result: f64 = 0.5f64 * (self.time as f64 - self._sqrt);
-        return result.ceil() as u128;
+        return result.floor() as u128 + 1;
     }
 
     fn maximum_charge_time(&self) -> u128 {
         let result: f64 = 0.5f64 * (self.time as f64 + self._sqrt);
-        return result.floor() as u128;
+        return (result.ceil() as u128).saturating_sub(1);
     }
 }
 
@@ -81,6 +99,23 @@ Distance:  9  40  200";
         assert_eq!(sut.number_of_ways_to_win(), 4);
     }
 
+    #[test]
+    fn test_numbers_of_ways_to_win_excludes_ties() {
+        let sut = Race::new(30, 200);
+
+        assert_eq!(sut.number_of_ways_to_win(), 9);
+    }
+
+    #[test]
+    fn test_numbers_of_ways_to_win_unwinnable_race() {
+        assert_eq!(Race::new(5, 10).number_of_ways_to_win(), 0);
+    }
+
+    #[test]
+    fn test_numbers_of_ways_to_win_tie_only_race() {
+        assert_eq!(Race::new(4, 4).number_of_ways_to_win(), 0);
+    }
+
     #[test]
     fn test_minimum_charge_time() {
         let sut = Race::new(7, 9);
@@ -1,6 +1,8 @@
+use std::fmt::{Display, Formatter};
+
 use phf::phf_map;
 
-use crate::GenericError;
+use crate::error::AocError;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Card {
@@ -36,38 +38,53 @@ static CARDS: phf::Map<u8, Card> = phf_map! {
 };
 
 impl Card {
-    pub fn parse_all(input: &str) -> Result<Vec<Card>, GenericError> {
+    pub const ALL: [Card; 13] = [
+        Card::Two,
+        Card::Three,
+        Card::Four,
+        Card::Five,
+        Card::Six,
+        Card::Seven,
+        Card::Eight,
+        Card::Nine,
+        Card::Ten,
+        Card::Jack,
+        Card::Queen,
+        Card::King,
+        Card::Ace,
+    ];
+
+    pub fn parse_all(input: &str) -> Result<Vec<Card>, AocError> {
         let mut result: Vec<Card> = Vec::new();
         for byte in input.as_bytes() {
             let card = CARDS
                 .get(byte)
-                .ok_or_else(|| GenericError::new("unable to parse card"))?;
+                .ok_or_else(|| AocError::parse_byte("unable to parse card", byte).locate(input))?;
             result.push(*card);
         }
 
         return Ok(result);
     }
+}
 
-    pub fn value(&self) -> u32 {
-        match self {
-            Card::Jack => 1,
-            Card::Two => 2,
-            Card::Three => 3,
-            Card::Four => 4,
-            Card::Five => 5,
-            Card::Six => 6,
-            Card::Seven => 7,
-            Card::Eight => 8,
-            Card::Nine => 9,
-            Card::Ten => 10,
-            Card::Queen => 12,
-            Card::King => 13,
-            Card::Ace => 14,
-        }
-    }
-
-    pub fn positional_value(&self, position: u32) -> u128 {
-        (self.value() << 4 * position) as u128
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Card::Ace => "A",
+            Card::King => "K",
+            Card::Queen => "Q",
+            Card::Jack => "J",
+            Card::Ten => "T",
+            Card::Nine => "9",
+            Card::Eight => "8",
+            Card::Seven => "7",
+            Card::Six => "6",
+            Card::Five => "5",
+            Card::Four => "4",
+            Card::Three => "3",
+            Card::Two => "2",
+        };
+        write!(f, "{symbol}")
     }
 }
 
@@ -75,6 +92,17 @@ impl Card {
 mod tests {
     use crate::day7::card::Card;
 
+    #[test]
+    fn test_display() {
+        let input = "23456789TJQKA";
+        let cards = Card::parse_all(input).unwrap();
+
+        assert_eq!(
+            cards.iter().map(|c| c.to_string()).collect::<String>(),
+            input
+        );
+    }
+
     #[test]
     fn test_parse_all() {
         let input = "23456789TJQKA";
@@ -99,11 +127,4 @@ mod tests {
             ]
         );
     }
-
-    #[test]
-    fn test_positional_value() {
-        let first = Card::Three.positional_value(1) + Card::Two.positional_value(0);
-        let second = Card::Two.positional_value(1) + Card::Ace.positional_value(0);
-        assert!(first > second);
-    }
 }
@@ -1,9 +1,14 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 use crate::day7::card::Card;
+use crate::day7::ruleset::Ruleset;
+use crate::error::AocError;
 use crate::string_functions::{split_and_clean, to_u128};
-use crate::GenericError;
+
+/// The most cards a hand can have, so that the values of all its cards fit into a `u128`.
+pub const MAX_CARDS: usize = 32;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Hand {
@@ -11,31 +16,56 @@ pub struct Hand {
     pub bid: u128,
     hand_type: HandType,
     card_value: u128,
+    joker_substitute: Option<Card>, // the card that the jokers of the hand count as
 }
 
 impl Hand {
-    pub fn parse_all(input: &str) -> Result<Vec<Hand>, GenericError> {
+    /// Parses one hand per line, all hands must have the same number of cards.
+    pub fn parse_all(input: &str, ruleset: &Ruleset) -> Result<Vec<Hand>, AocError> {
         let mut hands: Vec<Hand> = Vec::new();
         for line in input.lines() {
             let parts = split_and_clean(line, " ");
             if parts.len() != 2 {
-                return Err(GenericError::new("unable to parse hand"));
+                return Err(AocError::parse("unable to parse hand", line).locate(input));
+            }
+
+            let cards = Card::parse_all(parts[0]).map_err(|e| e.locate(input))?;
+            if let Some(first) = hands.first() {
+                if first.cards.len() != cards.len() {
+                    let message =
+                        format!("expected {} cards like the first hand", first.cards.len());
+                    return Err(AocError::parse(message, parts[0]).locate(input));
+                }
             }
 
-            let cards = Card::parse_all(parts[0])?;
-            let bid = to_u128(parts[1])?;
-            hands.push(Hand::new(cards, bid));
+            let bid = to_u128(parts[1]).map_err(|e| e.locate(input))?;
+            hands.push(Hand::new(cards, bid, ruleset).map_err(|e| e.locate(input))?);
         }
 
         return Ok(hands);
     }
 
-    fn new(cards: Vec<Card>, bid: u128) -> Hand {
-        let hand_type = HandType::from_cards(&cards);
+    /// Types and values the cards by the given rules, hands of different rules cannot be compared.
+    ///
+    /// A hand has between 1 and `MAX_CARDS` cards.
+    pub fn new(cards: Vec<Card>, bid: u128, ruleset: &Ruleset) -> Result<Hand, AocError> {
+        if cards.is_empty() || cards.len() > MAX_CARDS {
+            return Err(AocError::validation(format!(
+                "a hand must have between 1 and {MAX_CARDS} cards, but has {}",
+                cards.len()
+            )));
+        }
+
+        return Ok(Hand::with_valid_cards(cards, bid, ruleset));
+    }
+
+    fn with_valid_cards(cards: Vec<Card>, bid: u128, ruleset: &Ruleset) -> Hand {
+        let hand_type = HandType::from_cards(&cards, ruleset);
+        let joker_substitute = Hand::find_joker_substitute(&cards, ruleset);
         let card_value = cards
             .iter()
             .enumerate()
-            .map(|(i, c)| c.positional_value((cards.len() - 1 - i) as u32))
+            .map(|(i, c)| ruleset.positional_value(c, (cards.len() - 1 - i) as u32))
             .sum();
 
         return Hand {
@@ -43,13 +73,56 @@ impl Hand {
             bid,
             hand_type,
             card_value,
+            joker_substitute,
         };
     }
+
+    // the jokers join the most common other card, and the strongest of those if there is a tie
+    fn find_joker_substitute(cards: &[Card], ruleset: &Ruleset) -> Option<Card> {
+        if !cards.iter().any(|c| ruleset.is_joker(c)) {
+            return None;
+        }
+
+        let regular_cards = cards.iter().filter(|c| !ruleset.is_joker(c));
+        let substitute = regular_cards.max_by_key(|card| {
+            let count = cards.iter().filter(|c| c == card).count();
+            return (count, ruleset.value(card));
+        });
+
+        return Some(
+            substitute
+                .copied()
+                .unwrap_or(ruleset.strongest_regular_card()),
+        );
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        return &self.cards;
+    }
+
+    pub fn hand_type(&self) -> &HandType {
+        return &self.hand_type;
+    }
+
+    /// The strength of the cards by their position, with 4 bits per card and the first card
+    /// in the highest bits.
+    pub fn card_value(&self) -> u128 {
+        return self.card_value;
+    }
+
+    pub fn joker_substitute(&self) -> Option<Card> {
+        return self.joker_substitute;
+    }
+
+    /// The same hand under different rules.
+    pub fn with_ruleset(&self, ruleset: &Ruleset) -> Hand {
+        return Hand::with_valid_cards(self.cards.clone(), self.bid, ruleset);
+    }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        let type_order = self.hand_type.value().cmp(&other.hand_type.value());
+        let type_order = self.hand_type.cmp(&other.hand_type);
         if type_order != Ordering::Equal {
             return type_order;
         }
@@ -64,71 +137,90 @@ impl PartialOrd for Hand {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum HandType {
-    FiveOfAKind,
-    FourOfAKind,
-    FullHouse,
-    ThreeOfAKind,
-    TwoPairs,
-    OnePair,
-    HighCard,
+/// The type of a hand, given by how often its cards occur, from the most to the least common.
+///
+/// Types compare lexicographically by these counts, so `[5]` (five of a kind) beats `[4, 1]`
+/// (four of a kind), which beats `[3, 2]` (full house), and so on for hands of any size.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandType {
+    signature: Vec<u32>,
 }
 
 impl HandType {
-    fn from_cards(cards: &Vec<Card>) -> HandType {
-        if cards.len() != 5 {
-            panic!("each hand must consist of 5 cards exactly!");
-        }
+    /// Expects the counts to be sorted from the largest to the smallest.
+    pub fn from_signature(signature: &[u32]) -> HandType {
+        return HandType {
+            signature: signature.to_vec(),
+        };
+    }
 
-        let number_of_jokers = cards.iter().filter(|c| **c == Card::Jack).count() as u32;
+    pub fn five_of_a_kind() -> HandType {
+        return HandType::from_signature(&[5]);
+    }
 
-        let mut counts: HashMap<&Card, u32> = HashMap::new();
-        for card in cards.iter().filter(|c| **c != Card::Jack) {
-            *counts.entry(card).or_insert(0) += 1;
-        }
+    pub fn four_of_a_kind() -> HandType {
+        return HandType::from_signature(&[4, 1]);
+    }
 
-        let mut sorted_counts: Vec<&u32> = counts.values().collect::<Vec<&u32>>();
-        sorted_counts.sort_by(|c1, c2| c2.cmp(c1)); // sort in reverse order
+    pub fn full_house() -> HandType {
+        return HandType::from_signature(&[3, 2]);
+    }
 
-        if number_of_jokers == 5 || *sorted_counts[0] + number_of_jokers == 5 {
-            return HandType::FiveOfAKind;
-        }
+    pub fn three_of_a_kind() -> HandType {
+        return HandType::from_signature(&[3, 1, 1]);
+    }
 
-        if number_of_jokers == 4 || *sorted_counts[0] + number_of_jokers == 4 {
-            return HandType::FourOfAKind;
-        }
+    pub fn two_pairs() -> HandType {
+        return HandType::from_signature(&[2, 2, 1]);
+    }
 
-        if sorted_counts.len() > 1 && *sorted_counts[0] + *sorted_counts[1] + number_of_jokers == 5
-        {
-            return HandType::FullHouse;
-        }
+    pub fn one_pair() -> HandType {
+        return HandType::from_signature(&[2, 1, 1, 1]);
+    }
 
-        if *sorted_counts[0] + number_of_jokers == 3 {
-            return HandType::ThreeOfAKind;
-        }
+    pub fn high_card() -> HandType {
+        return HandType::from_signature(&[1, 1, 1, 1, 1]);
+    }
 
-        if *sorted_counts[0] + *sorted_counts[1] + number_of_jokers == 4 {
-            return HandType::TwoPairs;
+    fn from_cards(cards: &[Card], ruleset: &Ruleset) -> HandType {
+        let number_of_jokers = cards.iter().filter(|c| ruleset.is_joker(c)).count() as u32;
+
+        let mut counts: HashMap<&Card, u32> = HashMap::new();
+        for card in cards.iter().filter(|c| !ruleset.is_joker(c)) {
+            *counts.entry(card).or_insert(0) += 1;
         }
 
-        if *sorted_counts[0] + number_of_jokers == 2 {
-            return HandType::OnePair;
+        let mut signature = counts.into_values().collect::<Vec<u32>>();
+        signature.sort_by(|c1, c2| c2.cmp(c1)); // sort in reverse order
+
+        // the jokers join the most common card, which makes for the strongest hand
+        match signature.first_mut() {
+            Some(most_common) => *most_common += number_of_jokers,
+            None => signature.push(number_of_jokers),
         }
 
-        return HandType::HighCard;
+        return HandType { signature };
     }
 
-    pub fn value(&self) -> u32 {
-        match self {
-            HandType::HighCard => 1,
-            HandType::OnePair => 2,
-            HandType::TwoPairs => 3,
-            HandType::ThreeOfAKind => 4,
-            HandType::FullHouse => 5,
-            HandType::FourOfAKind => 6,
-            HandType::FiveOfAKind => 7,
-        }
+    pub fn signature(&self) -> &[u32] {
+        return &self.signature;
+    }
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self.signature.as_slice() {
+            [5] => "five of a kind",
+            [4, 1] => "four of a kind",
+            [3, 2] => "full house",
+            [3, 1, 1] => "three of a kind",
+            [2, 2, 1] => "two pairs",
+            [2, 1, 1, 1] => "one pair",
+            [1, 1, 1, 1, 1] => "high card",
+            signature => return write!(f, "{signature:?}"),
+        };
+
+        return write!(f, "{name}");
     }
 }
 
@@ -137,18 +229,39 @@ mod tests {
     use std::cmp::Ordering;
 
     use crate::day7::card::Card;
-    use crate::day7::hand::{Hand, HandType};
+    use crate::day7::hand::{Hand, HandType, MAX_CARDS};
+    use crate::day7::ruleset::Ruleset;
+    use crate::error::AocError;
+
+    #[test]
+    fn test_parse_all_reports_location_of_unknown_card() {
+        let input = "32T3K 765\nT55X5 684";
+        let actual = Hand::parse_all(input, &Ruleset::standard());
+
+        match actual {
+            Err(AocError::Parse(error)) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.column, Some(4));
+                assert_eq!(error.snippet, "X");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
 
     #[test]
     fn test_hand_card_value() {
         let first_hand = Hand::new(
             vec![Card::Three, Card::Two, Card::Two, Card::Two, Card::Two],
             28,
-        );
+            &Ruleset::jokers_wild(),
+        )
+        .unwrap();
         let second_hand = Hand::new(
             vec![Card::Two, Card::Ace, Card::Ace, Card::Ace, Card::Ace],
             56,
-        );
+            &Ruleset::jokers_wild(),
+        )
+        .unwrap();
 
         assert!(first_hand.card_value > second_hand.card_value);
     }
@@ -158,15 +271,21 @@ mod tests {
         let first_hand = Hand::new(
             vec![Card::Four, Card::Two, Card::Two, Card::Two, Card::Two],
             28,
-        );
+            &Ruleset::jokers_wild(),
+        )
+        .unwrap();
         let second_hand = Hand::new(
             vec![Card::Three, Card::Ace, Card::Ace, Card::Ace, Card::Ace],
             56,
-        );
+            &Ruleset::jokers_wild(),
+        )
+        .unwrap();
         let third_hand = Hand::new(
             vec![Card::Two, Card::Two, Card::Two, Card::Two, Card::Two],
             100,
-        );
+            &Ruleset::jokers_wild(),
+        )
+        .unwrap();
 
         assert_eq!(first_hand.cmp(&first_hand), Ordering::Equal);
         assert_eq!(second_hand.cmp(&second_hand), Ordering::Equal);
@@ -185,127 +304,236 @@ mod tests {
     #[test]
     fn test_hand_type_from_cards() {
         assert_eq!(
-            HandType::from_cards(&vec![Card::Ace, Card::Ace, Card::Ace, Card::Ace, Card::Ace]),
-            HandType::FiveOfAKind
+            HandType::from_cards(
+                &[Card::Ace, Card::Ace, Card::Ace, Card::Ace, Card::Ace],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::five_of_a_kind()
+        );
+        assert_eq!(
+            HandType::from_cards(
+                &[Card::Ace, Card::Ace, Card::Ace, Card::Ace, Card::Two],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::four_of_a_kind()
+        );
+        assert_eq!(
+            HandType::from_cards(
+                &[Card::Ace, Card::Ace, Card::Ace, Card::Two, Card::Two],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::full_house()
         );
         assert_eq!(
-            HandType::from_cards(&vec![Card::Ace, Card::Ace, Card::Ace, Card::Ace, Card::Two]),
-            HandType::FourOfAKind
+            HandType::from_cards(
+                &[Card::Ace, Card::Three, Card::Two, Card::Two, Card::Two],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::three_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&vec![Card::Ace, Card::Ace, Card::Ace, Card::Two, Card::Two]),
-            HandType::FullHouse
+            HandType::from_cards(
+                &[Card::Ace, Card::Ace, Card::Two, Card::Two, Card::Three],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::two_pairs()
         );
         assert_eq!(
-            HandType::from_cards(&vec![
-                Card::Ace,
-                Card::Three,
-                Card::Two,
-                Card::Two,
-                Card::Two,
-            ]),
-            HandType::ThreeOfAKind
+            HandType::from_cards(
+                &[Card::Ace, Card::Ace, Card::Two, Card::Four, Card::Three],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::one_pair()
         );
         assert_eq!(
-            HandType::from_cards(&vec![
-                Card::Ace,
-                Card::Ace,
-                Card::Two,
-                Card::Two,
-                Card::Three,
-            ]),
-            HandType::TwoPairs
+            HandType::from_cards(
+                &[Card::Ace, Card::King, Card::Two, Card::Four, Card::Three],
+                &Ruleset::jokers_wild()
+            ),
+            HandType::high_card()
+        );
+    }
+
+    #[test]
+    fn test_hand_type_without_jokers() {
+        assert_eq!(
+            HandType::from_cards(
+                &merge(&[repeat(Card::Ace, 4), repeat(Card::Jack, 1)]),
+                &Ruleset::standard()
+            ),
+            HandType::four_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&vec![
-                Card::Ace,
-                Card::Ace,
-                Card::Two,
-                Card::Four,
-                Card::Three,
-            ]),
-            HandType::OnePair
+            HandType::from_cards(
+                &[Card::Ace, Card::King, Card::Two, Card::Jack, Card::Three],
+                &Ruleset::standard()
+            ),
+            HandType::high_card()
         );
+    }
+
+    #[test]
+    fn test_hand_ordering_depends_on_ruleset() {
+        // both hands are one pair, unless jacks are jokers
+        let jacks = Hand::new(Card::parse_all("JJ234").unwrap(), 1, &Ruleset::standard()).unwrap();
+        let queens = Hand::new(Card::parse_all("QQ234").unwrap(), 2, &Ruleset::standard()).unwrap();
+
+        assert_eq!(jacks.cmp(&queens), Ordering::Less);
+
+        let ruleset = Ruleset::jokers_wild();
         assert_eq!(
-            HandType::from_cards(&vec![
-                Card::Ace,
-                Card::King,
-                Card::Two,
-                Card::Four,
-                Card::Three,
-            ]),
-            HandType::HighCard
+            jacks
+                .with_ruleset(&ruleset)
+                .cmp(&queens.with_ruleset(&ruleset)),
+            Ordering::Greater
         );
     }
 
+    #[test]
+    fn test_hand_type_of_larger_hands() {
+        let standard = Ruleset::standard();
+        let jokers_wild = Ruleset::jokers_wild();
+        let type_of = |cards: &str, ruleset: &Ruleset| {
+            HandType::from_cards(&Card::parse_all(cards).unwrap(), ruleset)
+        };
+
+        assert_eq!(type_of("AAAKKK", &standard).signature(), &[3, 3]);
+        assert_eq!(type_of("AAAAAAA", &standard).signature(), &[7]);
+        assert_eq!(type_of("22334JJ", &jokers_wild).signature(), &[4, 2, 1]);
+        assert_eq!(type_of("JJJJJJ", &jokers_wild).signature(), &[6]);
+
+        // the most common card decides first, then the second most common one and so on
+        assert!(type_of("AAAKK2", &standard) > type_of("AAKKQQ", &standard));
+        assert!(type_of("AAKKQQ", &standard) > type_of("AAKKQ2", &standard));
+    }
+
+    #[test]
+    fn test_hand_type_display() {
+        assert_eq!(HandType::full_house().to_string(), "full house");
+        assert_eq!(HandType::from_signature(&[3, 3]).to_string(), "[3, 3]");
+    }
+
+    #[test]
+    fn test_parse_all_with_six_cards() {
+        let hands = Hand::parse_all("AAKKQQ 10\nAAAKK2 20", &Ruleset::standard()).unwrap();
+
+        assert!(hands[0] < hands[1]);
+    }
+
+    #[test]
+    fn test_parse_all_with_hands_of_different_sizes() {
+        let actual = Hand::parse_all("32T3K 765\nT55J55 684", &Ruleset::standard());
+
+        match actual {
+            Err(AocError::Parse(error)) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.snippet, "T55J55");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_hand_size_limits() {
+        let ruleset = Ruleset::standard();
+
+        assert!(Hand::new(Vec::new(), 1, &ruleset).is_err());
+        assert!(Hand::new(vec![Card::Ace; MAX_CARDS], 1, &ruleset).is_ok());
+        assert!(Hand::new(vec![Card::Ace; MAX_CARDS + 1], 1, &ruleset).is_err());
+    }
+
     #[test]
     fn test_hand_type_five_of_a_kind_with_jokers() {
         assert_eq!(
-            HandType::from_cards(&merge(&[repeat(Card::Ace, 4), repeat(Card::Jack, 1)])),
-            HandType::FiveOfAKind
+            HandType::from_cards(
+                &merge(&[repeat(Card::Ace, 4), repeat(Card::Jack, 1)]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::five_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[repeat(Card::Ace, 3), repeat(Card::Jack, 2)])),
-            HandType::FiveOfAKind
+            HandType::from_cards(
+                &merge(&[repeat(Card::Ace, 3), repeat(Card::Jack, 2)]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::five_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[repeat(Card::Ace, 2), repeat(Card::Jack, 3)])),
-            HandType::FiveOfAKind
+            HandType::from_cards(
+                &merge(&[repeat(Card::Ace, 2), repeat(Card::Jack, 3)]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::five_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[repeat(Card::Ace, 1), repeat(Card::Jack, 4)])),
-            HandType::FiveOfAKind
+            HandType::from_cards(
+                &merge(&[repeat(Card::Ace, 1), repeat(Card::Jack, 4)]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::five_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[repeat(Card::Jack, 5)])),
-            HandType::FiveOfAKind
+            HandType::from_cards(&merge(&[repeat(Card::Jack, 5)]), &Ruleset::jokers_wild()),
+            HandType::five_of_a_kind()
         );
     }
 
     #[test]
     fn test_hand_type_four_of_a_kind_with_jokers() {
         assert_eq!(
-            HandType::from_cards(&merge(&[
-                repeat(Card::Ace, 3),
-                repeat(Card::Jack, 1),
-                repeat(Card::Two, 1)
-            ])),
-            HandType::FourOfAKind
+            HandType::from_cards(
+                &merge(&[
+                    repeat(Card::Ace, 3),
+                    repeat(Card::Jack, 1),
+                    repeat(Card::Two, 1)
+                ]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::four_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[
-                repeat(Card::Ace, 2),
-                repeat(Card::Jack, 2),
-                repeat(Card::Two, 1)
-            ])),
-            HandType::FourOfAKind
+            HandType::from_cards(
+                &merge(&[
+                    repeat(Card::Ace, 2),
+                    repeat(Card::Jack, 2),
+                    repeat(Card::Two, 1)
+                ]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::four_of_a_kind()
         );
         assert_eq!(
-            HandType::from_cards(&merge(&[
-                repeat(Card::Ace, 1),
-                repeat(Card::Jack, 3),
-                repeat(Card::Two, 1)
-            ])),
-            HandType::FourOfAKind
+            HandType::from_cards(
+                &merge(&[
+                    repeat(Card::Ace, 1),
+                    repeat(Card::Jack, 3),
+                    repeat(Card::Two, 1)
+                ]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::four_of_a_kind()
         );
     }
 
     #[test]
     fn test_hand_type_full_house_with_jokers() {
         assert_eq!(
-            HandType::from_cards(&merge(&[
-                repeat(Card::Ace, 2),
-                repeat(Card::King, 2),
-                repeat(Card::Jack, 1)
-            ])),
-            HandType::FullHouse
+            HandType::from_cards(
+                &merge(&[
+                    repeat(Card::Ace, 2),
+                    repeat(Card::King, 2),
+                    repeat(Card::Jack, 1)
+                ]),
+                &Ruleset::jokers_wild()
+            ),
+            HandType::full_house()
         );
     }
 
     fn repeat(card: Card, count: u32) -> Vec<Card> {
         let mut result: Vec<Card> = Vec::new();
         for _ in 0..count {
-            result.push(card.clone());
+            result.push(card);
         }
 
         return result;
@@ -315,7 +543,7 @@ mod tests {
         let mut result: Vec<Card> = Vec::new();
         for vec in vecs {
             for card in vec {
-                result.push(card.clone());
+                result.push(*card);
             }
         }
 
@@ -1,20 +1,138 @@
+use clap::{arg, Arg, ArgMatches};
+
 use crate::day7::hand::Hand;
-use std::error::Error;
-use std::fs;
+use crate::day7::ranking::{format_ranking, rank};
+use crate::day7::ruleset::Ruleset;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
+
+pub mod card;
+pub mod hand;
+pub mod ranking;
+pub mod ruleset;
+
+#[derive(Default)]
+pub struct Day7 {
+    /// The parts whose ranking is explained instead of solving them.
+    pub explain_parts: Vec<u32>,
+}
+
+impl Solver for Day7 {
+    type Input = Vec<Hand>;
+
+    fn explain_arguments(&self) -> Vec<Arg> {
+        // the explanation is always printed as text
+        return vec![
+            arg!(--explain "Print how the hands of the selected parts are ranked instead.")
+                .conflicts_with("format"),
+        ];
+    }
+
+    fn configure_explanation(&mut self, matches: &ArgMatches) {
+        self.explain_parts = if matches.get_flag("explain") {
+            crate::selected_parts(matches)
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn explain(&self, hands: &Vec<Hand>) -> Option<Result<String, AocError>> {
+        if self.explain_parts.is_empty() {
+            return None;
+        }
+
+        let mut result = String::new();
+        for part in &self.explain_parts {
+            let (name, ruleset) = match part {
+                1 => ("standard rules", Ruleset::standard()),
+                _ => ("jokers wild", Ruleset::jokers_wild()),
+            };
+            let hands = hands
+                .iter()
+                .map(|hand| hand.with_ruleset(&ruleset))
+                .collect::<Vec<Hand>>();
+
+            result.push_str(&format!("Challenge {part} - {name}\n"));
+            result.push_str(&format_ranking(&rank(&hands)));
+        }
+
+        return Some(Ok(result));
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Hand>, AocError> {
+        return Hand::parse_all(input, &Ruleset::standard());
+    }
+
+    fn part1(&self, hands: &Vec<Hand>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_winnings(hands)));
+    }
+
+    fn part2(&self, hands: &Vec<Hand>) -> Result<Answer, AocError> {
+        let ruleset = Ruleset::jokers_wild();
+        let hands = hands
+            .iter()
+            .map(|hand| hand.with_ruleset(&ruleset))
+            .collect::<Vec<Hand>>();
 
-mod card;
-mod hand;
+        return Ok(Answer::from(total_winnings(&hands)));
+    }
+}
+
+pub fn day7_challenge1(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn day7_challenge2(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge2);
+}
 
-pub fn day7_challenge1(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let mut hands = Hand::parse_all(&text)?;
-    hands.sort();
+pub fn challenge1(input: &str) -> Result<u128, AocError> {
+    return total_winnings_with(input, &Ruleset::standard());
+}
 
-    let mut result: u128 = 0;
-    for (index, hand) in hands.iter().enumerate() {
-        let rank = (index + 1) as u128;
-        result += hand.bid * rank;
+pub fn challenge2(input: &str) -> Result<u128, AocError> {
+    return total_winnings_with(input, &Ruleset::jokers_wild());
+}
+
+pub fn total_winnings_with(input: &str, ruleset: &Ruleset) -> Result<u128, AocError> {
+    let hands = Hand::parse_all(input, ruleset)?;
+    return Ok(total_winnings(&hands));
+}
+
+fn total_winnings(hands: &[Hand]) -> u128 {
+    return rank(hands).iter().map(|ranked| ranked.winnings).sum();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day7::card::Card;
+    use crate::day7::ruleset::Ruleset;
+    use crate::day7::{challenge1, challenge2, total_winnings_with};
+
+    static EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 6440);
     }
 
-    return Ok(result);
+    #[test]
+    fn test_challenge2_example() {
+        // jokers are wild
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_custom_card_order() {
+        // with the reversed order, low cards win ties between hands of the same type
+        let ruleset = Ruleset::new(Card::parse_all("AKQJT98765432").unwrap(), None).unwrap();
+
+        assert_eq!(total_winnings_with(EXAMPLE, &ruleset).unwrap(), 6833);
+    }
 }
@@ -0,0 +1,110 @@
+use crate::day7::hand::Hand;
+
+/// A hand together with its place among all hands.
+pub struct RankedHand<'a> {
+    pub hand: &'a Hand,
+    pub rank: u128,
+    pub winnings: u128, // bid * rank
+}
+
+/// Ranks the hands from the weakest to the strongest, starting with rank 1.
+pub fn rank(hands: &[Hand]) -> Vec<RankedHand<'_>> {
+    let mut hands = hands.iter().collect::<Vec<&Hand>>();
+    hands.sort();
+
+    return hands
+        .into_iter()
+        .enumerate()
+        .map(|(index, hand)| {
+            let rank = (index + 1) as u128;
+            return RankedHand {
+                hand,
+                rank,
+                winnings: hand.bid * rank,
+            };
+        })
+        .collect();
+}
+
+/// Shows why each hand got its rank, in the order of the ranking.
+pub fn format_ranking(ranking: &[RankedHand]) -> String {
+    let header = [
+        "Rank",
+        "Hand",
+        "Type",
+        "Card value",
+        "Jokers as",
+        "Bid",
+        "Winnings",
+    ]
+    .map(String::from);
+    let rows = ranking
+        .iter()
+        .map(|ranked| {
+            let hand = ranked.hand;
+            return [
+                ranked.rank.to_string(),
+                hand.cards().iter().map(|c| c.to_string()).collect(),
+                hand.hand_type().to_string(),
+                format!("{:#x}", hand.card_value()),
+                hand.joker_substitute()
+                    .map_or(String::from("-"), |c| c.to_string()),
+                hand.bid.to_string(),
+                ranked.winnings.to_string(),
+            ];
+        })
+        .collect::<Vec<[String; 7]>>();
+
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut result = format_row(&header, &widths);
+    for row in &rows {
+        result.push_str(&format_row(row, &widths));
+    }
+
+    let total: u128 = ranking.iter().map(|r| r.winnings).sum();
+    result.push_str(&format!("Total winnings: {total}\n"));
+
+    return result;
+}
+
+fn format_row(cells: &[String; 7], widths: &[usize; 7]) -> String {
+    let cells = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell:>width$}"))
+        .collect::<Vec<String>>();
+
+    return format!("{}\n", cells.join(" | "));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day7::hand::Hand;
+    use crate::day7::ranking::{format_ranking, rank};
+    use crate::day7::ruleset::Ruleset;
+
+    #[test]
+    fn test_format_ranking() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let hands = Hand::parse_all(input, &Ruleset::jokers_wild()).unwrap();
+
+        assert_eq!(
+            format_ranking(&rank(&hands)),
+            "\
+Rank |  Hand |           Type | Card value | Jokers as | Bid | Winnings
+   1 | 32T3K |       one pair |    0x32a3c |         - | 765 |      765
+   2 | KK677 |      two pairs |    0xcc677 |         - |  28 |       56
+   3 | T55J5 | four of a kind |    0xa5515 |         5 | 684 |     2052
+   4 | QQQJA | four of a kind |    0xbbb1d |         Q | 483 |     1932
+   5 | KTJJT | four of a kind |    0xca11a |         T | 220 |     1100
+Total winnings: 5905
+"
+        );
+    }
+}
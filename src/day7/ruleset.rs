@@ -0,0 +1,109 @@
+use crate::day7::card::Card;
+use crate::error::AocError;
+
+/// The rules by which hands are typed and compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    card_order: Vec<Card>, // from the weakest to the strongest card
+    joker: Option<Card>,
+}
+
+impl Ruleset {
+    /// Orders the cards as given, from the weakest to the strongest.
+    ///
+    /// The joker, if any, stands in for whichever card makes the hand strongest.
+    pub fn new(card_order: Vec<Card>, joker: Option<Card>) -> Result<Ruleset, AocError> {
+        for card in Card::ALL {
+            let occurrences = card_order.iter().filter(|c| **c == card).count();
+            if occurrences != 1 {
+                return Err(AocError::validation(format!(
+                    "the card order must contain every card once, but contains {card:?} {occurrences} times"
+                )));
+            }
+        }
+
+        return Ok(Ruleset { card_order, joker });
+    }
+
+    /// The rules of the first challenge, without jokers.
+    pub fn standard() -> Ruleset {
+        let card_order = Card::parse_all("23456789TJQKA").unwrap();
+        return Ruleset::new(card_order, None).unwrap();
+    }
+
+    /// The rules of the second challenge, where jacks are jokers and the weakest card.
+    pub fn jokers_wild() -> Ruleset {
+        let card_order = Card::parse_all("J23456789TQKA").unwrap();
+        return Ruleset::new(card_order, Some(Card::Jack)).unwrap();
+    }
+
+    pub fn joker(&self) -> Option<Card> {
+        return self.joker;
+    }
+
+    pub fn is_joker(&self, card: &Card) -> bool {
+        return self.joker == Some(*card);
+    }
+
+    /// The card that jokers stand in for when a hand has nothing but jokers.
+    pub fn strongest_regular_card(&self) -> Card {
+        return *self
+            .card_order
+            .iter()
+            .rev()
+            .find(|c| !self.is_joker(c))
+            .unwrap();
+    }
+
+    /// The strength of a card, starting with 1 for the weakest card.
+    pub fn value(&self, card: &Card) -> u32 {
+        let index = self.card_order.iter().position(|c| c == card).unwrap();
+        return index as u32 + 1;
+    }
+
+    pub fn positional_value(&self, card: &Card, position: u32) -> u128 {
+        return (self.value(card) as u128) << (4 * position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day7::card::Card;
+    use crate::day7::ruleset::Ruleset;
+
+    #[test]
+    fn test_value() {
+        let standard = Ruleset::standard();
+        let jokers_wild = Ruleset::jokers_wild();
+
+        assert_eq!(standard.value(&Card::Two), 1);
+        assert_eq!(standard.value(&Card::Jack), 10);
+        assert_eq!(standard.value(&Card::Ace), 13);
+        assert_eq!(jokers_wild.value(&Card::Jack), 1);
+        assert_eq!(jokers_wild.value(&Card::Two), 2);
+        assert!(!standard.is_joker(&Card::Jack));
+        assert_eq!(jokers_wild.strongest_regular_card(), Card::Ace);
+        assert!(jokers_wild.is_joker(&Card::Jack));
+    }
+
+    #[test]
+    fn test_positional_value() {
+        let sut = Ruleset::standard();
+        let first = sut.positional_value(&Card::Three, 1) + sut.positional_value(&Card::Two, 0);
+        let second = sut.positional_value(&Card::Two, 1) + sut.positional_value(&Card::Ace, 0);
+        assert!(first > second);
+    }
+
+    #[test]
+    fn test_custom_card_order() {
+        let sut = Ruleset::new(Card::parse_all("AKQJT98765432").unwrap(), None).unwrap();
+
+        assert!(sut.value(&Card::Two) > sut.value(&Card::Ace));
+    }
+
+    #[test]
+    fn test_incomplete_card_order() {
+        assert!(Ruleset::new(Card::parse_all("23456789TJQK").unwrap(), None).is_err());
+        assert!(Ruleset::new(Card::parse_all("23456789TJQKAA").unwrap(), None).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::GenericError;
+use crate::error::AocError;
 use phf::phf_map;
 
 static DIRECTIONS: phf::Map<u8, Direction> = phf_map! {
@@ -13,12 +13,12 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn parse_all(input: &str) -> Result<Vec<Direction>, GenericError> {
+    pub fn parse_all(input: &str) -> Result<Vec<Direction>, AocError> {
         let mut result: Vec<Direction> = Vec::new();
         for byte in input.as_bytes() {
             let direction = DIRECTIONS.get(byte);
             if direction.is_none() {
-                return Err(GenericError::new("unable to parse direction"));
+                return Err(AocError::parse_byte("unable to parse direction", byte).locate(input));
             }
 
             result.push(*direction.unwrap());
@@ -35,7 +35,7 @@ mod tests {
     #[test]
     fn test_parse_all() {
         let input = "LLRRLL";
-        let actual = Direction::parse_all(&input).unwrap();
+        let actual = Direction::parse_all(input).unwrap();
 
         assert_eq!(
             actual,
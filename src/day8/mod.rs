@@ -1,94 +1,366 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs;
-
-use crate::day8::direction::Direction;
-use crate::day8::node::{Map, Node};
-use crate::day8::prime::{Prime, PrimeFactor};
+use crate::day8::node::Map;
+use crate::day8::selector::NodeSelector;
 use crate::day8::tracer::Cycle;
-use crate::GenericError;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::number_theory::lcm_all;
+use crate::solver::{Answer, Solver};
 
 mod direction;
-mod node;
-mod prime;
+pub mod node;
+pub mod selector;
 mod tracer;
 
-pub fn day8_challenge1_naive(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let (direction, nodes, target_node_index) = parse_input(&text)?;
+pub struct Day8;
+
+impl Solver for Day8 {
+    // both challenges interpret the nodes differently, so they parse the text on their own
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        return Ok(input.to_string());
+    }
+
+    fn part1(&self, input: &String) -> Result<Answer, AocError> {
+        return Ok(Answer::from(challenge1(input)?));
+    }
+
+    fn part2(&self, input: &String) -> Result<Answer, AocError> {
+        return Ok(Answer::from(challenge2(input)?));
+    }
+}
+
+pub fn day8_challenge1_naive(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge1);
+}
 
-    let mut next_node_index = 0usize;
+pub fn day8_naive_with(
+    file_path: &str,
+    start: &NodeSelector,
+    target: &NodeSelector,
+) -> Result<u128, AocError> {
+    return solve_file(file_path, |input| naive_with(input, start, target));
+}
+
+/// Counts the steps from AAA to ZZZ by walking the map.
+pub fn challenge1(input: &str) -> Result<u128, AocError> {
+    return naive_with(
+        input,
+        &NodeSelector::exact("AAA"),
+        &NodeSelector::exact("ZZZ"),
+    );
+}
+
+/// Counts the steps until a walker from every start node stands on a target node, by walking
+/// the map step by step.
+pub fn naive_with(
+    input: &str,
+    start: &NodeSelector,
+    target: &NodeSelector,
+) -> Result<u128, AocError> {
+    let map = Map::parse_with(input, start, target)?;
+
+    // after as many steps as there are pairs of node and direction, every walker repeated one of
+    // them and is inside its cycle, only then are the cycles traced to know when to give up
+    let states = map.nodes.len() as u128 * map.directions.len() as u128;
+    let mut step_limit: Option<u128> = None;
+
+    let mut node_indices = map.start_indices.clone();
     let mut next_direction_index = 0usize;
     let mut steps = 0u128;
-    while next_node_index != target_node_index {
-        next_node_index = nodes[next_node_index].child_index(&direction[next_direction_index]);
-        next_direction_index = (next_direction_index + 1) % direction.len();
-        steps += 1;
+    while !node_indices
+        .iter()
+        .all(|index| map.target_indices.contains(index))
+    {
+        if steps == states {
+            step_limit = repetition_step(&map);
+        }
+
+        if step_limit.is_some_and(|limit| steps >= limit) {
+            return Err(no_common_hit(target));
+        }
+
+        let direction = &map.directions[next_direction_index];
+        for node_index in node_indices.iter_mut() {
+            *node_index = map.nodes[*node_index].child_index(direction);
+        }
+
+        next_direction_index = (next_direction_index + 1) % map.directions.len();
+        steps = steps
+            .checked_add(1)
+            .ok_or_else(|| AocError::overflow("the number of steps does not fit into 128 bits"))?;
     }
 
     return Ok(steps);
 }
 
-fn parse_input(input: &str) -> Result<(Vec<Direction>, Vec<Node>, usize), GenericError> {
-    let lines: Vec<&str> = input.lines().collect::<Vec<&str>>();
-    if lines.len() < 3 {
-        return Err(GenericError::new("no nodes in input"));
-    }
+/// The step from which on the positions of all walkers repeat, if it fits into 128 bits.
+///
+/// Once the last walker entered its cycle, the positions repeat after the least common multiple
+/// of the cycle lengths.
+fn repetition_step(map: &Map) -> Option<u128> {
+    let cycles = Cycle::find_all(map);
+    let lengths = cycles
+        .iter()
+        .map(|cycle| cycle.length)
+        .collect::<Vec<u128>>();
+    let latest_offset = cycles.iter().map(|cycle| cycle.offset).max().unwrap_or(0);
+
+    return lcm_all(&lengths).and_then(|lcm| lcm.checked_add(latest_offset));
+}
 
-    let directions = Direction::parse_all(lines[0])?;
-    let (nodes, target_node_index) = Node::parse_all(&lines[2..])?;
+fn no_common_hit(target: &NodeSelector) -> AocError {
+    return AocError::validation(format!(
+        "the walkers never stand on a target node {target} at the same time"
+    ));
+}
 
-    return Ok((directions, nodes, target_node_index));
+pub fn day8_challenge2_cycles(file_path: &str) -> Result<u128, AocError> {
+    return solve_file(file_path, challenge2);
 }
 
-pub fn day8_challenge2_cycles(file_path: &str) -> Result<u128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let map = Map::parse(&text)?;
+pub fn day8_cycles_with(
+    file_path: &str,
+    start: &NodeSelector,
+    target: &NodeSelector,
+) -> Result<u128, AocError> {
+    return solve_file(file_path, |input| cycles_with(input, start, target));
+}
+
+/// Counts the steps until all ghosts are on a node ending in Z, starting from the nodes ending
+/// in A.
+pub fn challenge2(input: &str) -> Result<u128, AocError> {
+    return cycles_with(
+        input,
+        &NodeSelector::suffix("A"),
+        &NodeSelector::suffix("Z"),
+    );
+}
+
+/// Counts the steps until a walker from every start node stands on a target node, based on the
+/// cycles of the walkers.
+pub fn cycles_with(
+    input: &str,
+    start: &NodeSelector,
+    target: &NodeSelector,
+) -> Result<u128, AocError> {
+    let map = Map::parse_with(input, start, target)?;
     let cycles = Cycle::find_all(&map);
 
-    for cycle in &cycles {
-        // checking assumptions for challenge
-        if cycle.repeated_results.len() != 1 {
-            return Err(Box::new(GenericError::new(
-                "found more than one result per cycle",
-            )));
-        }
+    return Cycle::first_common_hit(&cycles)?.ok_or_else(|| no_common_hit(target));
+}
 
-        if cycle.offset_results.len() != 0 {
-            return Err(Box::new(GenericError::new("found an offset result")));
-        }
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
 
-        if cycle.repeated_results[0] + cycle.offset != cycle.length {
-            return Err(Box::new(GenericError::new(
-                "initial target offset + cycle offset != cycle length",
-            )));
-        }
+    use crate::day8::selector::NodeSelector;
+    use crate::day8::{challenge1, challenge2, cycles_with, naive_with};
+    use crate::error::AocError;
+
+    const GHOST_EXAMPLE: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_challenge1_example() {
+        let input = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(challenge1(input).unwrap(), 2);
     }
 
-    let mut prime = Prime::new();
-    let mut prime_factors = Vec::new();
-    for cycle in &cycles {
-        prime_factors.push(prime.prime_factors(cycle.length));
+    #[test]
+    fn test_challenge1_repeated_directions_example() {
+        let input = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(challenge1(input).unwrap(), 6);
     }
 
-    return Ok(least_common_multiple(&prime_factors));
-}
+    #[test]
+    fn test_naive_with_selectors() {
+        let ghosts = NodeSelector::glob("[12][12]A").unwrap();
+        let target = NodeSelector::suffix("Z");
+        assert_eq!(naive_with(GHOST_EXAMPLE, &ghosts, &target).unwrap(), 6);
+
+        let first_ghost =
+            NodeSelector::predicate(|name| name.starts_with('1') && name.ends_with('A'));
+        let target = NodeSelector::prefix("11Z");
+        assert_eq!(naive_with(GHOST_EXAMPLE, &first_ghost, &target).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cycles_with_selectors() {
+        let first_ghost = NodeSelector::exact("11A");
+        let target = NodeSelector::exact("11Z");
+        assert_eq!(
+            cycles_with(GHOST_EXAMPLE, &first_ghost, &target).unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_unreachable_or_missing_nodes() {
+        let first_ghost = NodeSelector::exact("11A");
+        let target = NodeSelector::exact("22Z");
+        assert!(matches!(
+            naive_with(GHOST_EXAMPLE, &first_ghost, &target),
+            Err(AocError::Validation(_))
+        ));
+
+        let missing = NodeSelector::exact("33A");
+        assert!(matches!(
+            naive_with(GHOST_EXAMPLE, &missing, &target),
+            Err(AocError::Validation(message)) if message == "there is no start node named 33A"
+        ));
+    }
+
+    #[test]
+    fn test_naive_walkers_that_never_coincide() {
+        let input = "\
+L
+
+11A = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22D, 22D)
+22D = (22E, 22E)
+22E = (22Z, 22Z)
+22Z = (22A, 22A)";
 
-fn least_common_multiple(all_factors: &Vec<Vec<PrimeFactor>>) -> u128 {
-    let mut exponents: HashMap<u128, u128> = HashMap::new();
-    for factors in all_factors {
-        for factor in factors {
-            let maybe_existing_exponent = exponents.get(&factor.base);
-            if *maybe_existing_exponent.unwrap_or(&0) < factor.exponent {
-                exponents.insert(factor.base, factor.exponent);
+        // hits at 3 (mod 4) and 4 (mod 6)
+        let ghosts = NodeSelector::predicate(|name| name == "11A" || name == "22B");
+        assert!(matches!(
+            naive_with(input, &ghosts, &NodeSelector::suffix("Z")),
+            Err(AocError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_challenge2_example() {
+        // 22A reaches a target twice per cycle
+        assert_eq!(challenge2(GHOST_EXAMPLE).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_challenge2_offset_results() {
+        let input = "\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22A, 22A)";
+
+        // 11A only reaches its target before its cycle starts
+        assert_eq!(challenge2(input).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_challenge2_many_hits_in_coprime_cycles() {
+        // every walker enters a ring of prime length whose nodes are all targets
+        let mut input = String::from("L\n\n");
+        for length in [11, 13, 17, 19, 23, 29, 31] {
+            input.push_str(&format!("R{length}A = (R{length}N0Z, R{length}N0Z)\n"));
+            for index in 0..length {
+                let next = (index + 1) % length;
+                input.push_str(&format!(
+                    "R{length}N{index}Z = (R{length}N{next}Z, R{length}N{next}Z)\n"
+                ));
             }
         }
+
+        assert_eq!(challenge2(&input).unwrap(), 1);
     }
 
-    let mut result = 1u128;
-    for (k, v) in exponents {
-        result *= k.pow(v as u32);
+    #[test]
+    fn test_challenge2_cycles_sharing_a_factor() {
+        let input = "\
+L
+
+11A = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22D, 22D)
+22D = (22E, 22E)
+22E = (22Z, 22Z)
+22Z = (22A, 22A)";
+
+        // hits at 3 (mod 4) and 5 (mod 6)
+        assert_eq!(challenge2(input).unwrap(), 11);
+        assert_eq!(
+            naive_with(
+                input,
+                &NodeSelector::suffix("A"),
+                &NodeSelector::suffix("Z")
+            )
+            .unwrap(),
+            11
+        );
+
+        // hits at 3 (mod 4) and 4 (mod 6) never coincide
+        let ghosts = NodeSelector::predicate(|name| name == "11A" || name == "22B");
+        let target = NodeSelector::suffix("Z");
+        assert!(matches!(
+            cycles_with(input, &ghosts, &target),
+            Err(AocError::Validation(_))
+        ));
+        assert!(matches!(
+            naive_with(input, &ghosts, &target),
+            Err(AocError::Validation(_))
+        ));
     }
 
-    return result;
+    proptest! {
+        #[test]
+        fn test_naive_and_cycles_agree(
+            children in prop::collection::vec((0usize..8, 0usize..8), 8),
+            directions in "[LR]{1,4}",
+            starts in prop::collection::vec(any::<bool>(), 8),
+            targets in prop::collection::vec(any::<bool>(), 8),
+        ) {
+            let mut input = format!("{directions}\n\n");
+            for (index, (left, right)) in children.iter().enumerate() {
+                input.push_str(&format!("N{index} = (N{left}, N{right})\n"));
+            }
+
+            let is_start = move |name: &str| starts[name[1..].parse::<usize>().unwrap()];
+            let is_target = move |name: &str| targets[name[1..].parse::<usize>().unwrap()];
+            let start = NodeSelector::predicate(is_start);
+            let target = NodeSelector::predicate(is_target);
+
+            prop_assert_eq!(
+                naive_with(&input, &start, &target).ok(),
+                cycles_with(&input, &start, &target).ok()
+            );
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use crate::day8::direction::Direction;
+use crate::day8::selector::NodeSelector;
+use crate::error::AocError;
 use crate::string_functions::split_and_clean;
-use crate::GenericError;
 
 pub struct Map {
     pub directions: Vec<Direction>,
@@ -12,54 +13,59 @@ pub struct Map {
 }
 
 impl Map {
-    pub fn parse(input: &str) -> Result<Map, GenericError> {
+    /// Parses the map with the nodes ending in A as starts and the ones ending in Z as targets.
+    pub fn parse(input: &str) -> Result<Map, AocError> {
+        return Map::parse_with(
+            input,
+            &NodeSelector::suffix("A"),
+            &NodeSelector::suffix("Z"),
+        );
+    }
+
+    pub fn parse_with(
+        input: &str,
+        start: &NodeSelector,
+        target: &NodeSelector,
+    ) -> Result<Map, AocError> {
         let lines: Vec<&str> = input.lines().collect::<Vec<&str>>();
         if lines.len() < 3 {
-            return Err(GenericError::new("no nodes in input"));
+            return Err(AocError::parse("no nodes in input", input).locate(input));
         }
 
-        let directions = Direction::parse_all(lines[0])?;
-        let raw_nodes = Node::parse_raw_nodes(&lines[2..])?;
+        let directions = Direction::parse_all(lines[0]).map_err(|e| e.locate(input))?;
+        let raw_nodes = Node::parse_raw_nodes(&lines[2..]).map_err(|e| e.locate(input))?;
         let name_lookup = Node::create_node_name_to_index_lookup(&raw_nodes);
-        let nodes = Node::batch_create(&raw_nodes, &name_lookup)?;
+        let nodes = Node::batch_create(&raw_nodes, &name_lookup).map_err(|e| e.locate(input))?;
 
-        let start_node_names = name_lookup
-            .keys()
-            .filter(|k| k.ends_with("A"))
-            .map(|k| *k)
-            .collect::<Vec<&str>>();
-        let end_node_names = name_lookup
-            .keys()
-            .filter(|k| k.ends_with("Z"))
-            .map(|k| *k)
-            .collect::<Vec<&str>>();
+        let start_indices = Map::select(&raw_nodes, start);
+        if start_indices.is_empty() {
+            return Err(AocError::validation(format!(
+                "there is no start node {start}"
+            )));
+        }
 
-        let start_node_indices = Map::lookup_all(&start_node_names, &name_lookup)?;
-        let end_node_indices = Map::lookup_all(&end_node_names, &name_lookup)?;
+        let target_indices = Map::select(&raw_nodes, target);
+        if target_indices.is_empty() {
+            return Err(AocError::validation(format!(
+                "there is no target node {target}"
+            )));
+        }
 
         return Ok(Map {
             directions,
-            start_indices: start_node_indices,
-            target_indices: end_node_indices,
+            start_indices,
+            target_indices,
             nodes,
         });
     }
 
-    fn lookup_all(
-        names: &Vec<&str>,
-        lookup: &HashMap<&str, usize>,
-    ) -> Result<Vec<usize>, GenericError> {
-        let mut result: Vec<usize> = Vec::new();
-        for name in names {
-            let index = lookup.get(name);
-            if index.is_none() {
-                return Err(GenericError::new("unable to find index of node"));
-            }
-
-            result.push(*index.unwrap());
-        }
-
-        return Ok(result);
+    fn select(raw_nodes: &[RawNode], selector: &NodeSelector) -> Vec<usize> {
+        return raw_nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, raw_node)| selector.matches(raw_node.name))
+            .map(|(index, _)| index)
+            .collect();
     }
 }
 
@@ -78,31 +84,34 @@ struct RawNode<'a> {
 }
 
 impl Node {
-    pub fn parse_all(input: &[&str]) -> Result<(Vec<Node>, usize), GenericError> {
+    pub fn parse_all(input: &[&str]) -> Result<Vec<Node>, AocError> {
         let raw_nodes = Node::parse_raw_nodes(input)?;
         let name_lookup = Node::create_node_name_to_index_lookup(&raw_nodes);
 
-        let nodes = Node::batch_create(&raw_nodes, &name_lookup)?;
-
-        let target_node_index = name_lookup.get("ZZZ");
-        if target_node_index.is_none() {
-            return Err(GenericError::new("unable to find target node index"));
-        }
-
-        return Ok((nodes, *target_node_index.unwrap()));
+        return Node::batch_create(&raw_nodes, &name_lookup);
     }
 
     fn batch_create(
         raw_nodes: &Vec<RawNode>,
         name_lookup: &HashMap<&str, usize>,
-    ) -> Result<Vec<Node>, GenericError> {
+    ) -> Result<Vec<Node>, AocError> {
         let mut result: Vec<Node> = Vec::new();
         for (index, raw_node) in raw_nodes.iter().enumerate() {
             let left_index = name_lookup.get(raw_node.left_child_name);
             let right_index = name_lookup.get(raw_node.right_child_name);
 
-            if left_index.is_none() || right_index.is_none() {
-                return Err(GenericError::new("unable to find child node index"));
+            if left_index.is_none() {
+                return Err(AocError::parse(
+                    "unknown child node",
+                    raw_node.left_child_name,
+                ));
+            }
+
+            if right_index.is_none() {
+                return Err(AocError::parse(
+                    "unknown child node",
+                    raw_node.right_child_name,
+                ));
             }
 
             result.push(Node {
@@ -115,7 +124,7 @@ impl Node {
         return Ok(result);
     }
 
-    fn parse_raw_nodes<'a>(lines: &'a [&'a str]) -> Result<Vec<RawNode<'a>>, GenericError> {
+    fn parse_raw_nodes<'a>(lines: &'a [&'a str]) -> Result<Vec<RawNode<'a>>, AocError> {
         let mut result: Vec<RawNode> = Vec::new();
         for line in lines {
             result.push(Node::parse_raw_node(line)?);
@@ -124,18 +133,20 @@ impl Node {
         return Ok(result);
     }
 
-    fn parse_raw_node(input: &str) -> Result<RawNode, GenericError> {
+    fn parse_raw_node(input: &str) -> Result<RawNode<'_>, AocError> {
         let equal_split = split_and_clean(input, "=");
         if equal_split.len() != 2 {
-            return Err(GenericError::new(
+            return Err(AocError::parse(
                 "unable to extract node name and child nodes",
+                input,
             ));
         }
 
         let comma_split = split_and_clean(equal_split[1], ",");
         if comma_split.len() != 2 {
-            return Err(GenericError::new(
+            return Err(AocError::parse(
                 "unable to extract node name and child nodes",
+                equal_split[1],
             ));
         }
 
@@ -179,7 +190,7 @@ ZZZ = (ZZZ, ZZZ)"
             .lines()
             .collect::<Vec<&str>>();
 
-        let actual = Node::parse_all(&input).unwrap().0;
+        let actual = Node::parse_all(&input).unwrap();
 
         assert_eq!(
             actual,
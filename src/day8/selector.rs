@@ -0,0 +1,221 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::error::AocError;
+
+/// Selects nodes of the map by their name, e.g. the nodes the ghosts start from.
+pub enum NodeSelector {
+    Exact(String),
+    Prefix(String),
+    Suffix(String),
+    Glob(Glob),
+    Predicate(Box<dyn Fn(&str) -> bool>),
+}
+
+impl NodeSelector {
+    pub fn exact(name: &str) -> NodeSelector {
+        return NodeSelector::Exact(name.to_string());
+    }
+
+    pub fn prefix(prefix: &str) -> NodeSelector {
+        return NodeSelector::Prefix(prefix.to_string());
+    }
+
+    pub fn suffix(suffix: &str) -> NodeSelector {
+        return NodeSelector::Suffix(suffix.to_string());
+    }
+
+    pub fn glob(pattern: &str) -> Result<NodeSelector, AocError> {
+        return Ok(NodeSelector::Glob(Glob::parse(pattern)?));
+    }
+
+    pub fn predicate(predicate: impl Fn(&str) -> bool + 'static) -> NodeSelector {
+        return NodeSelector::Predicate(Box::new(predicate));
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NodeSelector::Exact(exact) => name == exact,
+            NodeSelector::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            NodeSelector::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            NodeSelector::Glob(glob) => glob.matches(name),
+            NodeSelector::Predicate(predicate) => predicate(name),
+        }
+    }
+}
+
+impl Display for NodeSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeSelector::Exact(exact) => write!(f, "named {exact}"),
+            NodeSelector::Prefix(prefix) => write!(f, "starting with {prefix}"),
+            NodeSelector::Suffix(suffix) => write!(f, "ending with {suffix}"),
+            NodeSelector::Glob(glob) => write!(f, "matching {}", glob.pattern),
+            NodeSelector::Predicate(_) => write!(f, "matching the predicate"),
+        }
+    }
+}
+
+impl Debug for NodeSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NodeSelector({self})")
+    }
+}
+
+/// A shell like pattern: `?` matches one character, `*` any number of characters and `[...]`
+/// one of a set of characters, e.g. `[A-C]` or `[!Z]`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<GlobToken>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum GlobToken {
+    Literal(char),
+    AnyChar,
+    AnyString,
+    Set {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+}
+
+impl Glob {
+    pub fn parse(pattern: &str) -> Result<Glob, AocError> {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '?' => GlobToken::AnyChar,
+                '*' => GlobToken::AnyString,
+                '[' => {
+                    let negated = chars.next_if_eq(&'!').is_some();
+                    let mut ranges = Vec::new();
+                    loop {
+                        let from = match chars.next() {
+                            None => return Err(AocError::parse("unclosed character set", pattern)),
+                            Some(']') if !ranges.is_empty() => break,
+                            Some(from) => from,
+                        };
+
+                        // a `-` right before the closing bracket is a literal
+                        let is_range =
+                            chars.peek() == Some(&'-') && chars.clone().nth(1) != Some(']');
+
+                        let mut to = from;
+                        if is_range {
+                            chars.next();
+                            to = match chars.next() {
+                                None => {
+                                    return Err(AocError::parse("unclosed character set", pattern))
+                                }
+                                Some(to) if to < from => {
+                                    return Err(AocError::parse(
+                                        "reversed character range",
+                                        pattern,
+                                    ))
+                                }
+                                Some(to) => to,
+                            };
+                        }
+
+                        ranges.push((from, to));
+                    }
+
+                    GlobToken::Set { ranges, negated }
+                }
+                literal => GlobToken::Literal(literal),
+            };
+
+            tokens.push(token);
+        }
+
+        return Ok(Glob {
+            pattern: pattern.to_string(),
+            tokens,
+        });
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        let chars = name.chars().collect::<Vec<char>>();
+
+        // matched[i] is true if the tokens so far match the first i characters
+        let mut matched = vec![false; chars.len() + 1];
+        matched[0] = true;
+        for token in &self.tokens {
+            let mut next = vec![false; chars.len() + 1];
+            for i in 0..=chars.len() {
+                next[i] = match token {
+                    GlobToken::AnyString => matched[i] || (i > 0 && next[i - 1]),
+                    _ => i > 0 && matched[i - 1] && token.matches(chars[i - 1]),
+                };
+            }
+
+            matched = next;
+        }
+
+        return matched[chars.len()];
+    }
+}
+
+impl GlobToken {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            GlobToken::Literal(literal) => c == *literal,
+            GlobToken::AnyChar => true,
+            GlobToken::AnyString => true,
+            GlobToken::Set { ranges, negated } => {
+                ranges.iter().any(|(from, to)| (*from..=*to).contains(&c)) != *negated
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day8::selector::{Glob, NodeSelector};
+
+    #[test]
+    fn test_matches() {
+        assert!(NodeSelector::exact("AAA").matches("AAA"));
+        assert!(!NodeSelector::exact("AAA").matches("AAAA"));
+        assert!(NodeSelector::prefix("11").matches("11A"));
+        assert!(!NodeSelector::prefix("11").matches("211"));
+        assert!(NodeSelector::suffix("Z").matches("22Z"));
+        assert!(!NodeSelector::suffix("Z").matches("Z22"));
+        assert!(NodeSelector::predicate(|name| name.len() == 2).matches("AB"));
+        assert!(!NodeSelector::predicate(|name| name.len() == 2).matches("ABC"));
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        let glob = Glob::parse("*A").unwrap();
+        assert!(glob.matches("A"));
+        assert!(glob.matches("11A"));
+        assert!(!glob.matches("11B"));
+
+        let glob = Glob::parse("?[0-9]*[!Z]").unwrap();
+        assert!(glob.matches("X1A"));
+        assert!(glob.matches("X12345B"));
+        assert!(!glob.matches("X1Z"));
+        assert!(!glob.matches("XX1A"));
+        assert!(!glob.matches("X1"));
+
+        let glob = Glob::parse("[A-]?").unwrap();
+        assert!(glob.matches("AB"));
+        assert!(glob.matches("-B"));
+        assert!(!glob.matches("BB"));
+
+        let glob = Glob::parse("[]A]*").unwrap();
+        assert!(glob.matches("]BC"));
+        assert!(glob.matches("ABC"));
+        assert!(!glob.matches("BC"));
+    }
+
+    #[test]
+    fn test_glob_parse_errors() {
+        assert!(Glob::parse("[AB").is_err());
+        assert!(Glob::parse("[A-").is_err());
+        assert!(Glob::parse("[Z-A]").is_err());
+    }
+}
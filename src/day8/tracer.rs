@@ -1,8 +1,15 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroU128;
 
 use crate::day8::node::Map;
+use crate::error::AocError;
+use crate::number_theory::{gcd, lcm_all, Congruence};
+
+/// The most combinations of target steps that are combined, any further walkers are checked
+/// step by step.
+const MAX_CONGRUENCES: usize = 1 << 16;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Cycle {
@@ -35,6 +42,136 @@ impl Cycle {
         return result;
     }
 
+    /// Finds the earliest step at which every walker stands on a target, if there is one.
+    pub fn first_common_hit(cycles: &[Cycle]) -> Result<Option<u128>, AocError> {
+        let Some(latest_offset) = cycles.iter().map(|cycle| cycle.offset).max() else {
+            return Ok(Some(0));
+        };
+        let lengths = cycles
+            .iter()
+            .map(|cycle| NonZeroU128::new(cycle.length))
+            .collect::<Option<Vec<NonZeroU128>>>()
+            .ok_or_else(|| AocError::validation("a cycle has to take at least one step"))?;
+
+        // before the latest offset, the hit has to be one of the offset results of that walker
+        let latest_cycle = cycles.iter().find(|c| c.offset == latest_offset).unwrap();
+        for step in &latest_cycle.offset_results {
+            if cycles.iter().all(|cycle| cycle.is_hit(*step)) {
+                return Ok(Some(*step));
+            }
+        }
+
+        // from there on, every walker is inside its cycle, so early hits are found quicker by
+        // walking than by combining the hits of all cycles
+        let longest_length = lengths.iter().max().unwrap();
+        for step in latest_offset..latest_offset + longest_length.get() {
+            if cycles.iter().all(|cycle| cycle.is_hit(step)) {
+                return Ok(Some(step));
+            }
+        }
+
+        // the other hits of a walker are the steps congruent to one of its repeated results,
+        // combining the walkers with the fewest of them first keeps the combinations few
+        let mut cycles = cycles
+            .iter()
+            .zip(lengths)
+            .collect::<Vec<(&Cycle, NonZeroU128)>>();
+        cycles.sort_by_key(|(cycle, _)| cycle.repeated_results.len());
+
+        let mut congruences = vec![Congruence::any()];
+        let mut combined_cycles = 0;
+        for (cycle, length) in &cycles {
+            let combinations = congruences.len() * cycle.repeated_results.len();
+            if combinations > MAX_CONGRUENCES {
+                break;
+            }
+
+            let mut combined = Vec::with_capacity(combinations);
+            for congruence in &congruences {
+                for result in &cycle.repeated_results {
+                    let hit = Congruence::new(cycle.offset + result, *length);
+                    combined.extend(congruence.combine(&hit)?);
+                }
+            }
+
+            combined.sort_by_key(|c| c.residue());
+            combined.dedup();
+            congruences = combined;
+            combined_cycles += 1;
+        }
+
+        let remaining = &cycles[combined_cycles..];
+        if remaining.is_empty() {
+            let mut result: Option<u128> = None;
+            for congruence in congruences {
+                let step = congruence.first_from(latest_offset)?;
+                result = Some(result.map_or(step, |r| r.min(step)));
+            }
+
+            return Ok(result);
+        }
+
+        return Cycle::first_hit_of_remaining(&congruences, remaining, latest_offset);
+    }
+
+    /// Walks the steps of the congruences in ascending order, until every remaining walker
+    /// stands on a target as well.
+    ///
+    /// The congruences all share the least common multiple of the combined cycle lengths as
+    /// their modulus, so their steps repeat in the same order every modulus steps.
+    fn first_hit_of_remaining(
+        congruences: &[Congruence],
+        remaining: &[(&Cycle, NonZeroU128)],
+        latest_offset: u128,
+    ) -> Result<Option<u128>, AocError> {
+        let Some(modulus) = congruences.first().map(|c| c.modulus().get()) else {
+            return Ok(None);
+        };
+
+        let mut first_steps = congruences
+            .iter()
+            .map(|congruence| congruence.first_from(latest_offset))
+            .collect::<Result<Vec<u128>, AocError>>()?;
+        first_steps.sort();
+
+        // after the least common multiple of all cycle lengths, the walkers repeat their hits
+        let remaining_lengths = remaining
+            .iter()
+            .map(|(_, length)| length.get())
+            .collect::<Vec<u128>>();
+        let rounds = lcm_all(&remaining_lengths)
+            .map(|lcm| lcm / gcd(lcm, modulus))
+            .unwrap_or(u128::MAX);
+
+        for round in 0..rounds {
+            for first_step in &first_steps {
+                let step = round
+                    .checked_mul(modulus)
+                    .and_then(|offset| offset.checked_add(*first_step))
+                    .ok_or_else(|| {
+                        AocError::overflow("the number of steps does not fit into 128 bits")
+                    })?;
+                if remaining.iter().all(|(cycle, _)| cycle.is_hit(step)) {
+                    return Ok(Some(step));
+                }
+            }
+        }
+
+        return Ok(None);
+    }
+
+    fn is_hit(&self, step: u128) -> bool {
+        if step < self.offset {
+            return self.offset_results.contains(&step);
+        }
+
+        // the repeated results are sorted, because they were found in the order of their steps
+        return self
+            .repeated_results
+            .binary_search(&((step - self.offset) % self.length))
+            .is_ok();
+    }
+
     fn find(map: &Map, start_node_index: usize) -> Cycle {
         let mut steps = 0u128;
         let mut next_node_index = start_node_index;
@@ -101,7 +238,7 @@ LR
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
-        let map = Map::parse(&input).unwrap();
+        let map = Map::parse(input).unwrap();
 
         let first_cycle = Cycle::find(&map, 0);
 
@@ -126,4 +263,51 @@ XXX = (XXX, XXX)";
             }
         )
     }
+
+    #[test]
+    fn test_too_many_congruences() {
+        // every walker hits its targets on odd steps, except for the last, which only hits them
+        // on even steps, so combining it first rules out all 11 * 13 * 17 * 19 * 23 combinations
+        let mut cycles = [11u128, 13, 17, 19, 23]
+            .iter()
+            .map(|prime| Cycle {
+                offset: 0,
+                length: 2 * prime,
+                offset_results: Vec::new(),
+                repeated_results: (1..2 * prime).step_by(2).collect(),
+            })
+            .collect::<Vec<Cycle>>();
+        cycles.push(Cycle {
+            offset: 0,
+            length: 2,
+            offset_results: Vec::new(),
+            repeated_results: vec![0],
+        });
+
+        assert_eq!(Cycle::first_common_hit(&cycles).unwrap(), None);
+        assert_eq!(Cycle::first_common_hit(&cycles[..5]).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_hit_beyond_too_many_congruences() {
+        // the walkers with odd hits have more combinations than are combined, so the last of
+        // them is checked step by step, and the first odd step that is 1000 (mod 1009) is 2009
+        let mut cycles = [11u128, 13, 17, 19, 23]
+            .iter()
+            .map(|prime| Cycle {
+                offset: 0,
+                length: 2 * prime,
+                offset_results: Vec::new(),
+                repeated_results: (1..2 * prime).step_by(2).collect(),
+            })
+            .collect::<Vec<Cycle>>();
+        cycles.push(Cycle {
+            offset: 0,
+            length: 1009,
+            offset_results: Vec::new(),
+            repeated_results: vec![1000],
+        });
+
+        assert_eq!(Cycle::first_common_hit(&cycles).unwrap(), Some(2009));
+    }
 }
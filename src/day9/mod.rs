@@ -1,29 +1,80 @@
 use crate::day9::sequence::SequenceExtrapolation;
-use std::error::Error;
-use std::fs;
+use crate::error::AocError;
+use crate::input::solve_file;
+use crate::solver::{Answer, Solver};
 
 mod sequence;
 
-pub fn day9_challenge1(file_path: &str) -> Result<i128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let extrapolations = SequenceExtrapolation::parse_all(&text)?;
+pub struct Day9;
 
+impl Solver for Day9 {
+    type Input = Vec<SequenceExtrapolation>;
+
+    fn parse(&self, input: &str) -> Result<Vec<SequenceExtrapolation>, AocError> {
+        return SequenceExtrapolation::parse_all(input);
+    }
+
+    fn part1(&self, extrapolations: &Vec<SequenceExtrapolation>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_next_values(extrapolations.clone())));
+    }
+
+    fn part2(&self, extrapolations: &Vec<SequenceExtrapolation>) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_of_previous_values(extrapolations.clone())));
+    }
+}
+
+pub fn day9_challenge1(file_path: &str) -> Result<i128, AocError> {
+    return solve_file(file_path, challenge1);
+}
+
+pub fn day9_challenge2(file_path: &str) -> Result<i128, AocError> {
+    return solve_file(file_path, challenge2);
+}
+
+pub fn challenge1(input: &str) -> Result<i128, AocError> {
+    let extrapolations = SequenceExtrapolation::parse_all(input)?;
+    return Ok(sum_of_next_values(extrapolations));
+}
+
+pub fn challenge2(input: &str) -> Result<i128, AocError> {
+    let extrapolations = SequenceExtrapolation::parse_all(input)?;
+    return Ok(sum_of_previous_values(extrapolations));
+}
+
+fn sum_of_next_values(extrapolations: Vec<SequenceExtrapolation>) -> i128 {
     let mut result = 0i128;
     for mut extrapolation in extrapolations {
         result += extrapolation.value_at_index(extrapolation.len());
     }
 
-    return Ok(result);
+    return result;
 }
 
-pub fn day9_challenge2(file_path: &str) -> Result<i128, Box<dyn Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let extrapolations = SequenceExtrapolation::parse_all(&text)?;
-
+fn sum_of_previous_values(extrapolations: Vec<SequenceExtrapolation>) -> i128 {
     let mut result = 0i128;
     for mut extrapolation in extrapolations {
         result += extrapolation.previous_value(1);
     }
 
-    return Ok(result);
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day9::{challenge1, challenge2};
+
+    static EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn test_challenge1_example() {
+        assert_eq!(challenge1(EXAMPLE).unwrap(), 114);
+    }
+
+    #[test]
+    fn test_challenge2_example() {
+        assert_eq!(challenge2(EXAMPLE).unwrap(), 2);
+    }
 }
@@ -1,21 +1,22 @@
+use crate::error::AocError;
 use crate::string_functions::{all_to_i128, split_and_clean};
-use crate::GenericError;
 
+#[derive(Clone)]
 pub struct SequenceExtrapolation {
     sequences: Vec<NumberSequence>,
 }
 
 impl SequenceExtrapolation {
-    pub fn parse_all(input: &str) -> Result<Vec<SequenceExtrapolation>, GenericError> {
+    pub fn parse_all(input: &str) -> Result<Vec<SequenceExtrapolation>, AocError> {
         let mut result = Vec::new();
         for line in input.lines() {
-            result.push(SequenceExtrapolation::from_line(line)?);
+            result.push(SequenceExtrapolation::from_line(line).map_err(|e| e.locate(input))?);
         }
 
         return Ok(result);
     }
 
-    fn from_line(input: &str) -> Result<SequenceExtrapolation, GenericError> {
+    fn from_line(input: &str) -> Result<SequenceExtrapolation, AocError> {
         let mut sequences = Vec::new();
         sequences.push(NumberSequence::parse(input)?);
 
@@ -29,7 +30,7 @@ impl SequenceExtrapolation {
     }
 
     pub fn value_at_index(&mut self, index: usize) -> i128 {
-        if self.sequences.len() < 1 {
+        if self.sequences.is_empty() {
             panic!("no sequences in extrapolator found");
         }
 
@@ -48,7 +49,7 @@ impl SequenceExtrapolation {
     }
 
     pub fn previous_value(&mut self, number_of_values: i32) -> i128 {
-        if self.sequences.len() < 1 {
+        if self.sequences.is_empty() {
             panic!("no sequences in extrapolator found");
         }
 
@@ -68,34 +69,34 @@ impl SequenceExtrapolation {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 struct NumberSequence {
     numbers: Vec<i128>,
     is_constant: bool,
 }
 
 impl NumberSequence {
-    fn parse(input: &str) -> Result<NumberSequence, GenericError> {
+    fn parse(input: &str) -> Result<NumberSequence, AocError> {
         let parts = split_and_clean(input, " ");
         let numbers = all_to_i128(&parts)?;
 
-        if numbers.len() < 1 {
-            return Err(GenericError::new("empty number sequence"));
+        if numbers.is_empty() {
+            return Err(AocError::parse("empty number sequence", input));
         }
 
         return Ok(NumberSequence::new(numbers));
     }
 
-    fn difference_sequence(sequence: &NumberSequence) -> Result<NumberSequence, GenericError> {
+    fn difference_sequence(sequence: &NumberSequence) -> Result<NumberSequence, AocError> {
         if sequence.numbers.len() < 2 {
-            return Err(GenericError::new(
+            return Err(AocError::validation(
                 "number sequence must contain at least 2 elements",
             ));
         }
 
         let mut differences = Vec::new();
         for i in 1..sequence.numbers.len() {
-            let difference = &sequence.numbers[i] - &sequence.numbers[i - 1];
+            let difference = sequence.numbers[i] - sequence.numbers[i - 1];
             differences.push(difference);
         }
 
@@ -103,7 +104,7 @@ impl NumberSequence {
     }
 
     fn new(numbers: Vec<i128>) -> NumberSequence {
-        if numbers.len() < 1 {
+        if numbers.is_empty() {
             panic!("empty number sequences are not supported")
         }
 
@@ -114,7 +115,7 @@ impl NumberSequence {
         };
     }
 
-    fn all_elements_equal(numbers: &Vec<i128>) -> bool {
+    fn all_elements_equal(numbers: &[i128]) -> bool {
         if numbers.len() < 2 {
             return true;
         }
@@ -168,7 +169,7 @@ mod tests {
     #[test]
     fn test_sequence_extrapolation_from_input_simple() {
         let input = "0   3   6   9  12  15";
-        let actual = SequenceExtrapolation::from_line(&input).unwrap();
+        let actual = SequenceExtrapolation::from_line(input).unwrap();
 
         assert_eq!(
             actual.sequences,
@@ -182,7 +183,7 @@ mod tests {
     #[test]
     fn test_sequence_extrapolation_from_input_harder() {
         let input = "10  13  16  21  30  45";
-        let actual = SequenceExtrapolation::from_line(&input).unwrap();
+        let actual = SequenceExtrapolation::from_line(input).unwrap();
 
         assert_eq!(
             actual.sequences,
@@ -198,7 +199,7 @@ mod tests {
     #[test]
     fn test_sequence_extrapolation_from_input_with_negatives() {
         let input = "3 2 1 0 -1 -2 -3";
-        let actual = SequenceExtrapolation::from_line(&input).unwrap();
+        let actual = SequenceExtrapolation::from_line(input).unwrap();
 
         assert_eq!(
             actual.sequences,
@@ -212,7 +213,7 @@ mod tests {
     #[test]
     fn test_value_at_index_simple() {
         let input = "0   3   6   9  12  15";
-        let mut sut = SequenceExtrapolation::from_line(&input).unwrap();
+        let mut sut = SequenceExtrapolation::from_line(input).unwrap();
 
         for i in 0..100 {
             assert_eq!(sut.value_at_index(i), i as i128 * 3);
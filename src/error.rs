@@ -0,0 +1,298 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+
+pub enum AocError {
+    Io { path: String, source: io::Error },
+    Parse(Box<ParseError>),
+    Validation(String),
+    UnsupportedAssumption(String),
+    Overflow(String),
+    Several(Vec<AocError>),
+    NotImplemented,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub snippet: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,   // 1-based
+    pub column: Option<usize>, // 1-based, counted in chars
+    line_text: Option<String>,
+    snippet_address: usize,
+    snippet_length: usize,
+}
+
+impl AocError {
+    pub fn io(path: &str, source: io::Error) -> AocError {
+        return AocError::Io {
+            path: path.to_string(),
+            source,
+        };
+    }
+
+    /// Creates a parse error for the given snippet.
+    ///
+    /// The snippet should be a sub slice of the puzzle input, so that `locate` can later determine
+    /// its line and column.
+    pub fn parse(message: impl Into<String>, snippet: &str) -> AocError {
+        return AocError::Parse(Box::new(ParseError {
+            message: message.into(),
+            snippet: snippet.to_string(),
+            file: None,
+            line: None,
+            column: None,
+            line_text: None,
+            snippet_address: snippet.as_ptr() as usize,
+            snippet_length: snippet.len(),
+        }));
+    }
+
+    pub fn parse_byte(message: impl Into<String>, byte: &u8) -> AocError {
+        let bytes = std::slice::from_ref(byte);
+        return AocError::Parse(Box::new(ParseError {
+            message: message.into(),
+            snippet: String::from_utf8_lossy(bytes).to_string(),
+            file: None,
+            line: None,
+            column: None,
+            line_text: None,
+            snippet_address: byte as *const u8 as usize,
+            snippet_length: 1,
+        }));
+    }
+
+    pub fn validation(message: impl Into<String>) -> AocError {
+        return AocError::Validation(message.into());
+    }
+
+    pub fn unsupported_assumption(message: impl Into<String>) -> AocError {
+        return AocError::UnsupportedAssumption(message.into());
+    }
+
+    /// Creates an error for a result that is too large for the integer type the solver uses.
+    pub fn overflow(message: impl Into<String>) -> AocError {
+        return AocError::Overflow(message.into());
+    }
+
+    /// Combines the problems found by a validation pass, a single problem is returned unchanged.
+    pub fn several(mut errors: Vec<AocError>) -> AocError {
+        if errors.len() == 1 {
+            return errors.pop().unwrap();
+        }
+
+        return AocError::Several(errors);
+    }
+
+    /// Determines line and column of a parse error within the given input.
+    ///
+    /// Errors whose snippet is not part of `input` are returned unchanged.
+    pub fn locate(self, input: &str) -> AocError {
+        match self {
+            AocError::Parse(mut error) => {
+                error.locate(input);
+                return AocError::Parse(error);
+            }
+            AocError::Several(errors) => {
+                return AocError::Several(errors.into_iter().map(|e| e.locate(input)).collect());
+            }
+            _ => return self,
+        }
+    }
+
+    pub fn in_file(self, file: &str) -> AocError {
+        match self {
+            AocError::Parse(mut error) => {
+                error.file = Some(file.to_string());
+                return AocError::Parse(error);
+            }
+            AocError::Several(errors) => {
+                return AocError::Several(errors.into_iter().map(|e| e.in_file(file)).collect());
+            }
+            _ => return self,
+        }
+    }
+}
+
+impl ParseError {
+    fn locate(&mut self, input: &str) {
+        let input_start = input.as_ptr() as usize;
+        let input_end = input_start + input.len();
+        if self.snippet_address < input_start
+            || self.snippet_address + self.snippet_length > input_end
+        {
+            return;
+        }
+
+        let bytes = input.as_bytes();
+        let offset = self.snippet_address - input_start;
+        let line_start = bytes[..offset]
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let line_end = bytes[offset..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|i| offset + i)
+            .unwrap_or(bytes.len());
+
+        let line = bytes[..line_start].iter().filter(|b| **b == b'\n').count() + 1;
+        let column = bytes[line_start..offset]
+            .iter()
+            .filter(|b| is_char_start(b))
+            .count()
+            + 1;
+
+        self.line = Some(line);
+        self.column = Some(column);
+        self.line_text = Some(
+            input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        );
+    }
+
+    fn write_excerpt(&self, f: &mut Formatter<'_>, line: usize, column: usize) -> std::fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        let line_text = self.line_text.as_deref().unwrap_or("");
+        let gutter = " ".repeat(line.to_string().len());
+        let available = line_text.chars().count().saturating_sub(column - 1);
+        let marker_length = self.snippet.chars().count().clamp(1, available.max(1));
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{gutter}--> {file}:{line}:{column}")?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {line_text}")?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(marker_length)
+        )
+    }
+}
+
+fn is_char_start(byte: &u8) -> bool {
+    return (byte & 0xC0) != 0x80;
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "unable to read {path}: {source}"),
+            AocError::Parse(error) => write!(f, "{error}"),
+            AocError::Validation(message) => write!(f, "invalid input: {message}"),
+            AocError::UnsupportedAssumption(message) => {
+                write!(f, "the input breaks an assumption of the solver: {message}")
+            }
+            AocError::Overflow(message) => write!(f, "arithmetic overflow: {message}"),
+            AocError::Several(errors) => {
+                write!(f, "found {} problems", errors.len())?;
+                for error in errors {
+                    write!(f, "\n{error}")?;
+                }
+
+                return Ok(());
+            }
+            AocError::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            return self.write_excerpt(f, line, column);
+        }
+
+        match &self.file {
+            Some(file) => write!(f, "{} in {file}: '{}'", self.message, self.snippet),
+            None => write!(f, "{}: '{}'", self.message, self.snippet),
+        }
+    }
+}
+
+impl Debug for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "An error occurred: {self}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+
+    #[test]
+    fn test_locate() {
+        let input = "32T3K 765\nT55J5 68a\nKK677 28";
+        let snippet = &input[16..19];
+        let error = AocError::parse("unable to parse bid", snippet).locate(input);
+
+        match error {
+            AocError::Parse(error) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.column, Some(7));
+                assert_eq!(error.snippet, "68a");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_locate_byte() {
+        let input = "..\n.x";
+        let error = AocError::parse_byte("unknown tile", &input.as_bytes()[4]).locate(input);
+
+        match error {
+            AocError::Parse(error) => {
+                assert_eq!(error.line, Some(2));
+                assert_eq!(error.column, Some(2));
+                assert_eq!(error.snippet, "x");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_locate_ignores_foreign_snippets() {
+        let input = "foo bar";
+        let error = AocError::parse("unexpected token", "bar").locate(input);
+
+        match error {
+            AocError::Parse(error) => {
+                assert_eq!(error.line, None);
+                assert_eq!(error.column, None);
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_display_excerpt() {
+        let input = "32T3K 765\nT55J5 68a";
+        let error = AocError::parse("unable to parse bid", &input[16..19])
+            .locate(input)
+            .in_file("day7.txt");
+
+        assert_eq!(
+            error.to_string(),
+            "\
+unable to parse bid
+ --> day7.txt:2:7
+  |
+2 | T55J5 68a
+  |       ^^^"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use std::fs;
+use std::io;
+use std::io::Read;
+
+use crate::error::AocError;
+
+/// The file path that stands for the standard input.
+pub const STDIN: &str = "-";
+
+/// Reads the given file, or the standard input if the path is `-`.
+pub fn read_file(file_path: &str) -> Result<String, AocError> {
+    if file_path == STDIN {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| AocError::io(display_name(file_path), e))?;
+        return Ok(text);
+    }
+
+    return fs::read_to_string(file_path).map_err(|e| AocError::io(file_path, e));
+}
+
+/// Reads the given file and passes its content to `solve`, attributing parse errors to the file.
+pub fn solve_file<T>(
+    file_path: &str,
+    solve: impl FnOnce(&str) -> Result<T, AocError>,
+) -> Result<T, AocError> {
+    let text = read_file(file_path)?;
+    return solve(&text).map_err(|e| e.in_file(display_name(file_path)));
+}
+
+pub fn display_name(file_path: &str) -> &str {
+    if file_path == STDIN {
+        return "<stdin>";
+    }
+
+    return file_path;
+}
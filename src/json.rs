@@ -0,0 +1,33 @@
+/// Quotes and escapes a value as a JSON string.
+pub fn string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::string;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("part1"), "\"part1\"");
+        assert_eq!(
+            string("unable \"to\"\n\\parse\u{1}"),
+            "\"unable \\\"to\\\"\\n\\\\parse\\u0001\""
+        );
+    }
+}
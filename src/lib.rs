@@ -1,144 +1,292 @@
-use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
+#![allow(clippy::needless_return, clippy::bool_assert_comparison)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use clap::ArgMatches;
 
-use crate::day10::solve_day10;
-use crate::day11::solve_day11;
-use crate::day12::solve_day12;
-use crate::day6::day6_challenge1;
-use crate::day7::day7_challenge1;
-use crate::day8::{day8_challenge1_naive, day8_challenge2_cycles};
-use crate::day9::{day9_challenge1, day9_challenge2};
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use crate::answers::Answers;
+use crate::error::AocError;
+use crate::input::{display_name, read_file};
+use crate::output::Format;
+use crate::registry::Day;
+use crate::runner::{find_input, format_table, run_all, run_part, PartRun, PARTS};
+
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod input;
+pub mod json;
+pub mod number_theory;
+pub mod output;
+pub mod registry;
+pub mod runner;
+pub mod solver;
 mod string_functions;
 
-pub struct GenericError {
-    message: &'static str,
-}
+/// Solves the selected parts of a day and prints the answers.
+///
+/// Returns false if the input could not be used or any implemented part failed.
+pub fn solve(day: &Day, sub_matches: &ArgMatches) -> bool {
+    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
+    let text = match read_file(file) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
+        }
+    };
 
-impl GenericError {
-    pub fn new(message: &'static str) -> GenericError {
-        GenericError { message }
-    }
+    let start = Instant::now();
+    let input = match day.parse(&text).map_err(|e| e.in_file(display_name(file))) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Unable to parse input of Day {}: {error}", day.number);
+            return false;
+        }
+    };
+    let parse_elapsed = start.elapsed();
 
-    pub fn not_implemented() -> GenericError {
-        GenericError {
-            message: "not implemented",
+    if let Some(explanation) = day.explain(input.as_ref()) {
+        match explanation {
+            Ok(explanation) => print!("{explanation}"),
+            Err(error) => {
+                eprintln!("Unable to explain Day {}: {error}", day.number);
+                return false;
+            }
         }
+        return true;
     }
-}
 
-impl Error for GenericError {}
+    let mut runs = Vec::new();
+    for part in selected_parts(sub_matches) {
+        let start = Instant::now();
+        let answer = match part {
+            1 => day.part1(input.as_ref()),
+            _ => day.part2(input.as_ref()),
+        };
 
-impl Display for GenericError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.message)
+        runs.push(PartRun {
+            day: day.number,
+            part,
+            answer: answer.map_err(|e| e.in_file(display_name(file))),
+            elapsed: parse_elapsed + start.elapsed(),
+        });
     }
-}
 
-impl Debug for GenericError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "An error occurred: {}", self.message)
+    match selected_format(sub_matches) {
+        Format::Text => runs.iter().for_each(print_result),
+        format => print_machine_readable(format, &runs),
     }
-}
 
-pub fn day1(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let maybe_result = day1::day1(file);
-    println!("Result of Day1 - Challenge 1: {}", maybe_result.unwrap());
+    return !runs.iter().any(PartRun::failed);
 }
 
-pub fn day2(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day2::day2_challenge1(file).unwrap();
-    let challenge2_result = day2::day2_challenge2(file).unwrap();
-    println!("Result of Day2 - Challenge 1: {}", challenge1_result);
-    println!("Result of Dat2 - Challenge 2: {}", challenge2_result);
+fn selected_format(sub_matches: &ArgMatches) -> Format {
+    return sub_matches
+        .get_one::<String>("format")
+        .and_then(|f| Format::from_name(f))
+        .unwrap_or(Format::Text);
 }
 
-pub fn day3(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day3::day3_challenge1(file).unwrap();
-    let challenge2_result = day3::day3_challenge2(file).unwrap();
+fn print_machine_readable(format: Format, runs: &[PartRun]) {
+    match format {
+        Format::Json => println!("{}", output::to_json(runs)),
+        _ => print!("{}", output::to_csv(runs)),
+    }
+}
 
-    println!("Result of Day 3 - Challenge 1: {}", challenge1_result);
-    println!("Result of Day 3 - Challenge 2: {}", challenge2_result);
+fn selected_parts(sub_matches: &ArgMatches) -> Vec<u32> {
+    return match sub_matches.get_one::<String>("part").map(|p| p.as_str()) {
+        Some("1") => vec![1],
+        Some("2") => vec![2],
+        _ => PARTS.to_vec(),
+    };
 }
 
-pub fn day4(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day4::day4_challenge1(file).unwrap();
-    let challenge2_result = day4::day4_challenge2(file).unwrap();
+/// Solves the selected days with the inputs of a directory and prints a summary.
+///
+/// Returns false if any implemented part failed, days without an input file are skipped.
+pub fn run(days: &[Day], sub_matches: &ArgMatches) -> bool {
+    let directory = sub_matches.get_one::<String>("DIR").expect("required arg");
+    let selected_days = select_days(days, sub_matches);
 
-    println!("Result of Day 4 - Challenge 1: {}", challenge1_result);
-    println!("Result of Day 4 - Challenge 2: {}", challenge2_result)
-}
+    let runs = run_all(&selected_days, Path::new(directory));
+    match selected_format(sub_matches) {
+        Format::Text => print!("{}", format_table(&runs)),
+        format => print_machine_readable(format, &runs),
+    }
 
-pub fn day5(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day5::day5_challenge1(file).unwrap();
-    let challenge2_result = day5::day5_challenge2(file).unwrap();
+    for run in runs.iter().filter(|run| run.failed()) {
+        if let Err(error) = &run.answer {
+            eprintln!("Day {} - Challenge {} failed: {error}", run.day, run.part);
+        }
+    }
 
-    println!("Result of Day 5 - Challenge 1: {}", challenge1_result);
-    println!("Result of Day 5 - Challenge 2: {}", challenge2_result);
+    return !runs.iter().any(PartRun::failed);
 }
 
-pub fn day6(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day6_challenge1(file).unwrap();
+/// Compares the answers of the selected days with the answers file, or records them.
+///
+/// Returns false if any answer did not match or the answers file could not be used.
+pub fn verify(days: &[Day], sub_matches: &ArgMatches) -> bool {
+    let directory = Path::new(sub_matches.get_one::<String>("DIR").expect("required arg"));
+    let answers_file = match sub_matches.get_one::<String>("answers") {
+        Some(file) => PathBuf::from(file),
+        None => directory.join("answers.txt"),
+    };
+    let answers_file = answers_file.to_string_lossy().to_string();
+    let record = sub_matches.get_flag("record");
+    let selected_days = select_days(days, sub_matches);
 
-    println!("Result of Day 6 - Challenge 1: {}", challenge1_result);
-}
+    let answers = if !record || Path::new(&answers_file).exists() {
+        let answers = read_file(&answers_file)
+            .and_then(|text| Answers::parse(&text).map_err(|e| e.in_file(&answers_file)));
+        match answers {
+            Ok(answers) => answers,
+            Err(error) => {
+                eprintln!("{error}");
+                return false;
+            }
+        }
+    } else {
+        Answers::new()
+    };
 
-pub fn day7(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day7_challenge1(file).unwrap();
+    if record {
+        return record_answers(&selected_days, directory, answers, &answers_file);
+    }
 
-    println!("Result of Day 7 - Challenge 1: {}", challenge1_result);
+    return verify_answers(&selected_days, directory, &answers);
 }
 
-pub fn day8(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day8_challenge1_naive(file).unwrap();
-    let challenge2_result = day8_challenge2_cycles(file).unwrap();
+fn record_answers(
+    days: &[&Day],
+    directory: &Path,
+    mut answers: Answers,
+    answers_file: &str,
+) -> bool {
+    let mut recorded = 0;
+    for run in run_all(days, directory) {
+        match run.answer {
+            Ok(answer) => {
+                answers.insert(run.day, run.part, &answer);
+                recorded += 1;
+            }
+            Err(error) => eprintln!(
+                "Not recording Day {} - Challenge {}: {error}",
+                run.day, run.part
+            ),
+        }
+    }
 
-    println!("Result of Day 8 - Challenge 1: {}", challenge1_result);
-    println!("Result of Day 8 - Challenge 2: {}", challenge2_result);
+    if let Err(error) = fs::write(answers_file, answers.to_string()) {
+        eprintln!("Unable to write {answers_file}: {error}");
+        return false;
+    }
+
+    println!("Recorded {recorded} answers in {answers_file}");
+    return true;
 }
 
-pub fn day9(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    let challenge1_result = day9_challenge1(file).unwrap();
-    let challenge2_result = day9_challenge2(file).unwrap();
+fn verify_answers(days: &[&Day], directory: &Path, answers: &Answers) -> bool {
+    let mut passed = 0;
+    let mut failed = 0;
+    for day in days {
+        for part in PARTS {
+            let expected = match answers.get(day.number, part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+
+            let label = format!("Day {} - Challenge {part}", day.number);
+            let actual = match find_input(directory, day, part) {
+                Some(file) => run_part(day, part, &file).answer,
+                None => Err(AocError::validation(format!(
+                    "no input found in {}",
+                    directory.display()
+                ))),
+            };
+
+            match actual {
+                Ok(answer) if answer.to_string() == expected => {
+                    println!("PASS {label}: {answer}");
+                    passed += 1;
+                }
+                Ok(answer) => {
+                    println!("FAIL {label}: expected {expected} but got {answer}");
+                    failed += 1;
+                }
+                Err(error) => {
+                    println!("FAIL {label}: {error}");
+                    failed += 1;
+                }
+            }
+        }
+    }
 
-    println!("Result of Day 9 - Challenge 1: {}", challenge1_result);
-    println!("Result of Day 9 - Challenge 2: {}", challenge2_result);
+    println!("{passed} passed, {failed} failed");
+    return failed == 0;
 }
 
-pub fn day10(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    solve_day10(file);
+fn select_days<'a>(days: &'a [Day], sub_matches: &ArgMatches) -> Vec<&'a Day> {
+    return match sub_matches.get_many::<String>("DAYS") {
+        Some(names) => {
+            let names = names.collect::<Vec<&String>>();
+            days.iter()
+                .filter(|d| names.iter().any(|n| *n == d.name))
+                .collect::<Vec<&Day>>()
+        }
+        None => days.iter().collect::<Vec<&Day>>(),
+    };
 }
 
-pub fn day11(sub_matches: &ArgMatches) {
+pub fn benchmark(day: &Day, sub_matches: &ArgMatches) -> bool {
     let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    solve_day11(file);
+    let iterations = *sub_matches
+        .get_one::<u32>("iterations")
+        .expect("has default");
+    let measurements = read_file(file).and_then(|text| {
+        return bench::bench(day, &text, iterations).map_err(|e| e.in_file(display_name(file)));
+    });
+
+    match measurements {
+        Ok(measurements) if sub_matches.get_flag("json") => {
+            println!("{}", bench::format_json(day, iterations, &measurements));
+        }
+        Ok(measurements) => print!("{}", bench::format_text(day, iterations, &measurements)),
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
+        }
+    }
+
+    return true;
 }
 
-pub fn day12(sub_matches: &ArgMatches) {
-    let file = sub_matches.get_one::<String>("FILE").expect("required arg");
-    solve_day12(file);
+fn print_result(run: &PartRun) {
+    match &run.answer {
+        Ok(answer) => println!(
+            "Result of Day {} - Challenge {}: {answer}",
+            run.day, run.part
+        ),
+        Err(AocError::NotImplemented) => eprintln!(
+            "Skipping Day {} - Challenge {}: not implemented",
+            run.day, run.part
+        ),
+        Err(error) => eprintln!("Day {} - Challenge {} failed: {error}", run.day, run.part),
+    }
 }
@@ -1,89 +1,108 @@
-use clap::{arg, Command};
+use std::process::ExitCode;
 
-use advent_of_code::{day1, day10, day11, day12, day2, day3, day4, day5, day6, day7, day8, day9};
+use clap::builder::PossibleValuesParser;
+use clap::{arg, value_parser, Arg, ArgGroup, Command};
 
-fn cli() -> Command {
+use advent_of_code::output::Format;
+use advent_of_code::registry::Day;
+use advent_of_code::{benchmark, registry, run, solve, verify};
+
+fn cli(days: &[Day]) -> Command {
     Command::new("aoc")
         .subcommand_required(true)
         .arg_required_else_help(true)
+        .subcommands(days.iter().map(|day| {
+            Command::new(day.name)
+                .arg(arg!(<FILE> "The input file for the challenge, or - for stdin."))
+                .arg(
+                    arg!(--part <PART> "The part(s) to solve.")
+                        .value_parser(["1", "2", "both"])
+                        .default_value("both"),
+                )
+                .arg(format_arg())
+                .args(day.arguments())
+                .args(day.explain_arguments())
+                .arg_required_else_help(true)
+        }))
         .subcommand(
-            Command::new("day1")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day2")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day3")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day4")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day5")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day6")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day7")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day8")
-                .arg(arg!(<FILE> "The input file for the challenge."))
+            Command::new("run")
+                .about("Runs several days and prints a summary table.")
+                .arg(arg!(<DIR> "The directory containing inputs like day7-challenge1.txt."))
+                .arg(arg!(--all "Run every registered day."))
+                .arg(days_arg(days))
+                .arg(format_arg())
+                .group(
+                    ArgGroup::new("selection")
+                        .args(["all", "DAYS"])
+                        .required(true),
+                )
                 .arg_required_else_help(true),
         )
         .subcommand(
-            Command::new("day9")
-                .arg(arg!(<FILE> "The input file for the challenge."))
+            Command::new("verify")
+                .about("Compares the answers with the recorded ones.")
+                .arg(arg!(<DIR> "The directory containing inputs like day7-challenge1.txt."))
+                .arg(arg!(--answers <FILE> "The answers file, defaults to DIR/answers.txt."))
+                .arg(arg!(--record "Write the current answers to the answers file."))
+                .arg(days_arg(days))
                 .arg_required_else_help(true),
         )
         .subcommand(
-            Command::new("day10")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day11")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
-        )
-        .subcommand(
-            Command::new("day12")
-                .arg(arg!(<FILE> "The input file for the challenge."))
-                .arg_required_else_help(true),
+            Command::new("bench")
+                .about("Times parsing, part 1 and part 2 of a day.")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommands(days.iter().map(|day| {
+                    Command::new(day.name)
+                        .arg(arg!(<FILE> "The input file for the challenge, or - for stdin."))
+                        .arg(
+                            arg!(--iterations <N> "How often each stage is run.")
+                                .value_parser(value_parser!(u32).range(1..))
+                                .default_value("10"),
+                        )
+                        .arg(arg!(--json "Print the results as JSON."))
+                        .args(day.arguments())
+                        .arg_required_else_help(true)
+                })),
         )
 }
 
-fn main() {
-    let matches = cli().get_matches();
+fn days_arg(days: &[Day]) -> Arg {
+    arg!([DAYS] ... "The days to use, e.g. day5 day7.")
+        .value_parser(PossibleValuesParser::new(days.iter().map(|d| d.name)))
+}
+
+fn format_arg() -> Arg {
+    arg!(--format <FORMAT> "How the answers are printed.")
+        .value_parser(Format::NAMES)
+        .default_value("text")
+}
+
+fn main() -> ExitCode {
+    let mut days = registry::all();
+    let matches = cli(&days).get_matches();
 
-    match matches.subcommand() {
-        Some(("day1", sub_matches)) => day1(sub_matches),
-        Some(("day2", sub_matches)) => day2(sub_matches),
-        Some(("day3", sub_matches)) => day3(sub_matches),
-        Some(("day4", sub_matches)) => day4(sub_matches),
-        Some(("day5", sub_matches)) => day5(sub_matches),
-        Some(("day6", sub_matches)) => day6(sub_matches),
-        Some(("day7", sub_matches)) => day7(sub_matches),
-        Some(("day8", sub_matches)) => day8(sub_matches),
-        Some(("day9", sub_matches)) => day9(sub_matches),
-        Some(("day10", sub_matches)) => day10(sub_matches),
-        Some(("day11", sub_matches)) => day11(sub_matches),
-        Some(("day12", sub_matches)) => day12(sub_matches),
+    let success = match matches.subcommand() {
+        Some(("run", sub_matches)) => run(&days, sub_matches),
+        Some(("verify", sub_matches)) => verify(&days, sub_matches),
+        Some(("bench", sub_matches)) => {
+            let (name, day_matches) = sub_matches.subcommand().expect("subcommand required");
+            let day = days.iter_mut().find(|d| d.name == name).unwrap();
+            day.configure(day_matches);
+            benchmark(day, day_matches)
+        }
+        Some((name, sub_matches)) => {
+            let day = days.iter_mut().find(|d| d.name == name).unwrap();
+            day.configure(sub_matches);
+            day.configure_explanation(sub_matches);
+            solve(day, sub_matches)
+        }
         _ => unreachable!(),
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
 }
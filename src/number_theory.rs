@@ -0,0 +1,493 @@
+use std::num::NonZeroU128;
+
+use crate::error::AocError;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct PrimeFactor {
+    pub base: u128,
+    pub exponent: u32,
+}
+
+/// All numbers `x` with `x % modulus == residue`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Congruence {
+    residue: u128,
+    modulus: NonZeroU128,
+}
+
+impl Congruence {
+    pub fn new(residue: u128, modulus: NonZeroU128) -> Congruence {
+        return Congruence {
+            residue: residue % modulus,
+            modulus,
+        };
+    }
+
+    /// The congruence every number satisfies.
+    pub fn any() -> Congruence {
+        return Congruence::new(0, NonZeroU128::MIN);
+    }
+
+    /// The smallest number of the congruence.
+    pub fn residue(&self) -> u128 {
+        return self.residue;
+    }
+
+    pub fn modulus(&self) -> NonZeroU128 {
+        return self.modulus;
+    }
+
+    /// Combines both congruences with the Chinese Remainder Theorem, the moduli do not need to be
+    /// coprime. Returns `None` if no number satisfies both.
+    pub fn combine(&self, other: &Congruence) -> Result<Option<Congruence>, AocError> {
+        // the gcd and the reduced modulus are not zero, because both moduli are not
+        let divisor = gcd(self.modulus.get(), other.modulus.get());
+        let difference = sub_mod(other.residue, self.residue, other.modulus);
+        if !difference.is_multiple_of(divisor) {
+            return Ok(None);
+        }
+
+        // x = self.residue + self.modulus * k, with self.modulus * k = difference (mod other.modulus)
+        let reduced_modulus = NonZeroU128::new(other.modulus.get() / divisor).unwrap();
+        let inverse = modular_inverse(self.modulus.get() / divisor, reduced_modulus).unwrap();
+        let k = mul_mod(difference / divisor, inverse, reduced_modulus);
+
+        let modulus = self
+            .modulus
+            .checked_mul(reduced_modulus)
+            .ok_or_else(|| AocError::overflow("the combined modulus does not fit into 128 bits"))?;
+
+        return Ok(Some(Congruence {
+            residue: self.residue + self.modulus.get() * k,
+            modulus,
+        }));
+    }
+
+    /// The smallest number of the congruence that is at least `minimum`.
+    pub fn first_from(&self, minimum: u128) -> Result<u128, AocError> {
+        if self.residue >= minimum {
+            return Ok(self.residue);
+        }
+
+        let modulus = self.modulus.get();
+        return (minimum - self.residue)
+            .div_ceil(modulus)
+            .checked_mul(modulus)
+            .and_then(|steps| steps.checked_add(self.residue))
+            .ok_or_else(|| AocError::overflow("the number does not fit into 128 bits"));
+    }
+}
+
+pub fn gcd(a: u128, b: u128) -> u128 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    return a;
+}
+
+/// The least common multiple, or `None` if it does not fit into 128 bits.
+pub fn lcm(a: u128, b: u128) -> Option<u128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    return (a / gcd(a, b)).checked_mul(b);
+}
+
+/// The least common multiple of all numbers, or `None` if it does not fit into 128 bits.
+pub fn lcm_all(numbers: &[u128]) -> Option<u128> {
+    return numbers
+        .iter()
+        .try_fold(1u128, |result, number| lcm(result, *number));
+}
+
+/// Returns `(gcd, x, y)` with `a * x + b * y == gcd`, or `None` if the gcd does not fit into an
+/// `i128`, which only happens for `i128::MIN` and 0.
+pub fn extended_gcd(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1i128, 0i128);
+    let (mut y0, mut y1) = (0i128, 1i128);
+    while r1 != 0 {
+        // the next coefficients are only needed if the remainder is not 0, they could be as
+        // large as 2^127 otherwise, and so could the quotient of i128::MIN and -1
+        let remainder = r0.wrapping_rem(r1);
+        if remainder == 0 {
+            (x0, y0) = (x1, y1);
+        } else {
+            let quotient = r0 / r1;
+            (x0, x1) = (x1, x0.checked_sub(quotient.checked_mul(x1)?)?);
+            (y0, y1) = (y1, y0.checked_sub(quotient.checked_mul(y1)?)?);
+        }
+
+        (r0, r1) = (r1, remainder);
+    }
+
+    if r0 < 0 {
+        return Some((r0.checked_neg()?, x0.checked_neg()?, y0.checked_neg()?));
+    }
+
+    return Some((r0, x0, y0));
+}
+
+/// The inverse of `a` modulo `modulus`, or `None` if they are not coprime.
+pub fn modular_inverse(a: u128, modulus: NonZeroU128) -> Option<u128> {
+    // extended Euclid, keeping the coefficient of `a` modulo `modulus` so it never turns negative
+    let (mut r0, mut r1) = (modulus.get(), a % modulus);
+    let (mut t0, mut t1) = (0u128, 1u128 % modulus);
+    while r1 != 0 {
+        let quotient = r0 / r1;
+        (r0, r1) = (r1, r0 - quotient * r1);
+        (t0, t1) = (t1, sub_mod(t0, mul_mod(quotient, t1, modulus), modulus));
+    }
+
+    if r0 != 1 {
+        return None;
+    }
+
+    return Some(t0);
+}
+
+/// Adds two numbers modulo `modulus` without overflowing.
+pub fn add_mod(a: u128, b: u128, modulus: NonZeroU128) -> u128 {
+    let (a, b) = (a % modulus, b % modulus);
+    if a >= modulus.get() - b {
+        return a - (modulus.get() - b);
+    }
+
+    return a + b;
+}
+
+/// Subtracts two numbers modulo `modulus`.
+pub fn sub_mod(a: u128, b: u128, modulus: NonZeroU128) -> u128 {
+    let (a, b) = (a % modulus, b % modulus);
+    if a >= b {
+        return a - b;
+    }
+
+    return modulus.get() - (b - a);
+}
+
+/// Multiplies two numbers modulo `modulus` without overflowing.
+pub fn mul_mod(a: u128, b: u128, modulus: NonZeroU128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let (mut a, mut b) = (a % modulus, b);
+    let mut result = 0u128;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+
+    return result;
+}
+
+pub fn pow_mod(base: u128, exponent: u128, modulus: NonZeroU128) -> u128 {
+    let (mut base, mut exponent) = (base % modulus, exponent);
+    let mut result = 1u128 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    return result;
+}
+
+const SMALL_PRIMES: [u128; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// Miller-Rabin with the first 15 primes as bases, which is exact below 3.3 * 10^24 and has no
+/// known counterexample above.
+pub fn is_prime(number: u128) -> bool {
+    let Some(modulus) = NonZeroU128::new(number).filter(|n| n.get() >= 2) else {
+        return false;
+    };
+
+    for prime in SMALL_PRIMES {
+        if number.is_multiple_of(prime) {
+            return number == prime;
+        }
+    }
+
+    let shift = (number - 1).trailing_zeros();
+    let odd_part = (number - 1) >> shift;
+    'bases: for base in SMALL_PRIMES {
+        let mut x = pow_mod(base, odd_part, modulus);
+        if x == 1 || x == number - 1 {
+            continue;
+        }
+
+        for _ in 1..shift {
+            x = mul_mod(x, x, modulus);
+            if x == number - 1 {
+                continue 'bases;
+            }
+        }
+
+        return false;
+    }
+
+    return true;
+}
+
+/// The prime factors of `number` in ascending order, found by trial division by small primes
+/// and Pollard's rho for the rest.
+pub fn prime_factors(number: u128) -> Vec<PrimeFactor> {
+    let mut primes = Vec::new();
+    if let Some(mut rest) = NonZeroU128::new(number) {
+        for prime in SMALL_PRIMES {
+            while rest.get().is_multiple_of(prime) {
+                primes.push(prime);
+                // a multiple of a prime divided by it is not zero
+                rest = NonZeroU128::new(rest.get() / prime).unwrap();
+            }
+        }
+
+        collect_prime_factors(rest, &mut primes);
+    }
+
+    primes.sort();
+
+    let mut result: Vec<PrimeFactor> = Vec::new();
+    for prime in primes {
+        match result.last_mut() {
+            Some(factor) if factor.base == prime => factor.exponent += 1,
+            _ => result.push(PrimeFactor {
+                base: prime,
+                exponent: 1,
+            }),
+        }
+    }
+
+    return result;
+}
+
+fn collect_prime_factors(number: NonZeroU128, primes: &mut Vec<u128>) {
+    if number == NonZeroU128::MIN {
+        return;
+    }
+
+    if is_prime(number.get()) {
+        primes.push(number.get());
+        return;
+    }
+
+    let divisor = pollard_rho(number);
+    collect_prime_factors(divisor, primes);
+    // a divisor of a number is at most as large as it
+    collect_prime_factors(NonZeroU128::new(number.get() / divisor).unwrap(), primes);
+}
+
+/// Finds a non-trivial divisor of a composite `number` without small prime factors, using
+/// Brent's cycle detection.
+fn pollard_rho(number: NonZeroU128) -> NonZeroU128 {
+    for increment in 1u128.. {
+        let step = |x: u128| add_mod(mul_mod(x, x, number), increment, number);
+
+        let (mut x, mut y) = (2u128, 2u128);
+        let mut divisor = 1u128;
+        let mut power = 1u128;
+        let mut length = 0u128;
+        while divisor == 1 {
+            if power == length {
+                x = y;
+                power *= 2;
+                length = 0;
+            }
+
+            y = step(y);
+            length += 1;
+            divisor = gcd(x.abs_diff(y), number.get());
+        }
+
+        // the gcd with a non-zero number is never zero
+        if divisor != number.get() {
+            return NonZeroU128::new(divisor).unwrap();
+        }
+    }
+
+    unreachable!("every composite number has a non-trivial divisor");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU128;
+
+    use crate::error::AocError;
+    use crate::number_theory::{
+        add_mod, extended_gcd, gcd, is_prime, lcm, lcm_all, modular_inverse, mul_mod, pow_mod,
+        prime_factors, sub_mod, Congruence, PrimeFactor,
+    };
+
+    // the largest prime below 2^128
+    const LARGEST_PRIME: u128 = u128::MAX - 158;
+
+    fn modulus(modulus: u128) -> NonZeroU128 {
+        return NonZeroU128::new(modulus).unwrap();
+    }
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(1 << 100, 3 << 30), Some(3 << 100));
+        assert_eq!(lcm(1 << 100, (1 << 100) - 1), None);
+        assert_eq!(lcm_all(&[2, 3, 4, 5, 6]), Some(60));
+        assert_eq!(lcm_all(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), Some((2, -9, 47)));
+        assert_eq!(extended_gcd(-12, 18), Some((6, 1, 1)));
+        assert_eq!(extended_gcd(7, 0), Some((7, 1, 0)));
+        assert_eq!(
+            extended_gcd(i128::MIN, 3),
+            Some((1, 1, -(i128::MIN / 3) + 1))
+        );
+        assert_eq!(
+            extended_gcd(3, i128::MIN),
+            Some((1, -(i128::MIN / 3) + 1, 1))
+        );
+        assert_eq!(extended_gcd(i128::MIN, 0), None);
+        assert_eq!(extended_gcd(i128::MIN, -1), Some((1, 0, -1)));
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(modular_inverse(3, modulus(11)), Some(4));
+        assert_eq!(modular_inverse(4, modulus(6)), None);
+        assert_eq!(modular_inverse(5, modulus(1)), Some(0));
+        assert_eq!(pow_mod(3, 200, modulus(1_000_000_007)), 136_318_165);
+
+        assert_eq!(
+            mul_mod(LARGEST_PRIME - 1, LARGEST_PRIME - 1, modulus(LARGEST_PRIME)),
+            1
+        );
+        assert_eq!(
+            modular_inverse(LARGEST_PRIME - 1, modulus(LARGEST_PRIME)),
+            Some(LARGEST_PRIME - 1)
+        );
+        let inverse = modular_inverse(12345, modulus(LARGEST_PRIME)).unwrap();
+        assert_eq!(mul_mod(inverse, 12345, modulus(LARGEST_PRIME)), 1);
+
+        // the operands do not have to be reduced
+        assert_eq!(add_mod(u128::MAX, u128::MAX, modulus(7)), 6);
+        assert_eq!(sub_mod(2, u128::MAX, modulus(7)), 6);
+        assert_eq!(mul_mod(u128::MAX, 2, modulus(u128::MAX - 1)), 2);
+    }
+
+    #[test]
+    fn test_combine_congruences() {
+        let actual = Congruence::new(2, modulus(3))
+            .combine(&Congruence::new(3, modulus(5)))
+            .unwrap();
+        assert_eq!(actual, Some(Congruence::new(8, modulus(15))));
+
+        // the moduli share the factor 2
+        let actual = Congruence::new(3, modulus(4))
+            .combine(&Congruence::new(5, modulus(6)))
+            .unwrap();
+        assert_eq!(actual, Some(Congruence::new(11, modulus(12))));
+
+        let actual = Congruence::new(3, modulus(4))
+            .combine(&Congruence::new(4, modulus(6)))
+            .unwrap();
+        assert_eq!(actual, None);
+
+        let actual = Congruence::any()
+            .combine(&Congruence::new(4, modulus(6)))
+            .unwrap();
+        assert_eq!(actual, Some(Congruence::new(4, modulus(6))));
+
+        assert!(matches!(
+            Congruence::new(0, modulus(1 << 100))
+                .combine(&Congruence::new(0, modulus((1 << 100) - 1))),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_first_from() {
+        assert_eq!(Congruence::new(11, modulus(12)).first_from(5).unwrap(), 11);
+        assert_eq!(Congruence::new(11, modulus(12)).first_from(12).unwrap(), 23);
+        assert_eq!(Congruence::new(11, modulus(12)).first_from(23).unwrap(), 23);
+        assert!(matches!(
+            Congruence::new(5, modulus(u128::MAX)).first_from(6),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes = (0u128..100).filter(|n| is_prime(*n)).collect::<Vec<u128>>();
+        assert_eq!(
+            primes,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+
+        assert!(is_prime(LARGEST_PRIME));
+        assert!(is_prime(18_446_744_073_709_551_557)); // the largest prime below 2^64
+        assert!(!is_prime(3_215_031_751)); // a strong pseudoprime to the bases 2, 3, 5 and 7
+        assert!(!is_prime(u128::MAX));
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        let factor = |base, exponent| PrimeFactor { base, exponent };
+
+        assert_eq!(prime_factors(0), vec![]);
+        assert_eq!(prime_factors(1), vec![]);
+        assert_eq!(prime_factors(21), vec![factor(3, 1), factor(7, 1)]);
+        assert_eq!(
+            prime_factors(3528),
+            vec![factor(2, 3), factor(3, 2), factor(7, 2)]
+        );
+        assert_eq!(prime_factors(8011), vec![factor(8011, 1)]);
+        assert_eq!(prime_factors(1 << 127), vec![factor(2, 127)]);
+
+        // 2^128 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 * 274177 * 6700417 * 67280421310721
+        assert_eq!(
+            prime_factors(u128::MAX),
+            vec![
+                factor(3, 1),
+                factor(5, 1),
+                factor(17, 1),
+                factor(257, 1),
+                factor(641, 1),
+                factor(65537, 1),
+                factor(274177, 1),
+                factor(6700417, 1),
+                factor(67280421310721, 1),
+            ]
+        );
+
+        assert_eq!(
+            prime_factors(1_000_000_007 * 998_244_353),
+            vec![factor(998_244_353, 1), factor(1_000_000_007, 1)]
+        );
+        assert_eq!(
+            prime_factors(1_000_000_007 * 18_446_744_073_709_551_557),
+            vec![
+                factor(1_000_000_007, 1),
+                factor(18_446_744_073_709_551_557, 1)
+            ]
+        );
+    }
+}
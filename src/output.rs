@@ -0,0 +1,152 @@
+use crate::json;
+use crate::runner::PartRun;
+
+/// Machine readable formats share the columns day, part, answer, answer_type and duration_ns.
+///
+/// Failed parts have the answer type `error` and the error message as answer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 3] = ["text", "json", "csv"];
+
+    pub fn from_name(name: &str) -> Option<Format> {
+        return match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        };
+    }
+}
+
+struct Record {
+    day: u32,
+    part: u32,
+    answer: String,
+    answer_type: &'static str,
+    duration_ns: u128,
+}
+
+impl Record {
+    fn new(run: &PartRun) -> Record {
+        let (answer, answer_type) = match &run.answer {
+            Ok(answer) => (answer.to_string(), answer.type_name()),
+            Err(error) => (error.to_string(), "error"),
+        };
+
+        return Record {
+            day: run.day,
+            part: run.part,
+            answer,
+            answer_type,
+            duration_ns: run.elapsed.as_nanos(),
+        };
+    }
+}
+
+pub fn to_json(runs: &[PartRun]) -> String {
+    let records = runs
+        .iter()
+        .map(|run| {
+            let record = Record::new(run);
+            return format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"answer_type\":{},\"duration_ns\":{}}}",
+                record.day,
+                record.part,
+                json::string(&record.answer),
+                json::string(record.answer_type),
+                record.duration_ns
+            );
+        })
+        .collect::<Vec<String>>();
+
+    return format!("[{}]", records.join(","));
+}
+
+pub fn to_csv(runs: &[PartRun]) -> String {
+    let mut result = String::from("day,part,answer,answer_type,duration_ns\n");
+    for run in runs {
+        let record = Record::new(run);
+        result.push_str(&format!(
+            "{},{},{},{},{}\n",
+            record.day,
+            record.part,
+            csv_field(&record.answer),
+            record.answer_type,
+            record.duration_ns
+        ));
+    }
+
+    return result;
+}
+
+fn csv_field(value: &str) -> String {
+    if !value.contains([',', '"', '\n', '\r']) {
+        return value.to_string();
+    }
+
+    return format!("\"{}\"", value.replace('"', "\"\""));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::error::AocError;
+    use crate::output::{to_csv, to_json};
+    use crate::runner::PartRun;
+    use crate::solver::Answer;
+
+    fn runs() -> Vec<PartRun> {
+        return vec![
+            PartRun {
+                day: 9,
+                part: 2,
+                answer: Ok(Answer::Signed(-3)),
+                elapsed: Duration::from_micros(12),
+            },
+            PartRun {
+                day: 11,
+                part: 1,
+                answer: Ok(Answer::Unsigned(374)),
+                elapsed: Duration::from_nanos(500),
+            },
+            PartRun {
+                day: 7,
+                part: 2,
+                answer: Err(AocError::validation("a \"bad\", input")),
+                elapsed: Duration::from_nanos(1),
+            },
+        ];
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&runs()),
+            "[\
+{\"day\":9,\"part\":2,\"answer\":\"-3\",\"answer_type\":\"signed\",\"duration_ns\":12000},\
+{\"day\":11,\"part\":1,\"answer\":\"374\",\"answer_type\":\"unsigned\",\"duration_ns\":500},\
+{\"day\":7,\"part\":2,\"answer\":\"invalid input: a \\\"bad\\\", input\",\"answer_type\":\"error\",\"duration_ns\":1}\
+]"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&runs()),
+            "\
+day,part,answer,answer_type,duration_ns
+9,2,-3,signed,12000
+11,1,374,unsigned,500
+7,2,\"invalid input: a \"\"bad\"\", input\",error,1
+"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::any::Any;
+
+use clap::{Arg, ArgMatches};
+
+use crate::day1::Day1;
+use crate::day10::Day10;
+use crate::day11::Day11;
+use crate::day12::Day12;
+use crate::day2::Day2;
+use crate::day3::Day3;
+use crate::day4::Day4;
+use crate::day5::Day5;
+use crate::day6::Day6;
+use crate::day7::Day7;
+use crate::day8::Day8;
+use crate::day9::Day9;
+use crate::error::AocError;
+use crate::solver::{Answer, Solver};
+
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+    solver: Box<dyn AnySolver>,
+}
+
+impl Day {
+    fn new<S: Solver + 'static>(number: u32, name: &'static str, solver: S) -> Day {
+        return Day {
+            number,
+            name,
+            solver: Box::new(solver),
+        };
+    }
+
+    /// The command line arguments specific to this day.
+    pub fn arguments(&self) -> Vec<Arg> {
+        return self.solver.arguments();
+    }
+
+    pub fn configure(&mut self, matches: &ArgMatches) {
+        self.solver.configure(matches);
+    }
+
+    /// The command line arguments that only affect the explanation of this day.
+    pub fn explain_arguments(&self) -> Vec<Arg> {
+        return self.solver.explain_arguments();
+    }
+
+    pub fn configure_explanation(&mut self, matches: &ArgMatches) {
+        self.solver.configure_explanation(matches);
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        return self.solver.parse(input);
+    }
+
+    pub fn explain(&self, input: &dyn Any) -> Option<Result<String, AocError>> {
+        return self.solver.explain(input);
+    }
+
+    pub fn part1(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        return self.solver.part1(input);
+    }
+
+    pub fn part2(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        return self.solver.part2(input);
+    }
+}
+
+/// All days that can be solved, in calendar order.
+pub fn all() -> Vec<Day> {
+    return vec![
+        Day::new(1, "day1", Day1),
+        Day::new(2, "day2", Day2),
+        Day::new(3, "day3", Day3),
+        Day::new(4, "day4", Day4),
+        Day::new(5, "day5", Day5),
+        Day::new(6, "day6", Day6),
+        Day::new(7, "day7", Day7::default()),
+        Day::new(8, "day8", Day8),
+        Day::new(9, "day9", Day9),
+        Day::new(10, "day10", Day10),
+        Day::new(11, "day11", Day11),
+        Day::new(12, "day12", Day12::default()),
+    ];
+}
+
+pub fn find(name: &str) -> Option<Day> {
+    return all().into_iter().find(|d| d.name == name);
+}
+
+// object safe counterpart of `Solver`, so that days with different inputs can live in one list
+trait AnySolver {
+    fn arguments(&self) -> Vec<Arg>;
+    fn configure(&mut self, matches: &ArgMatches);
+    fn explain_arguments(&self) -> Vec<Arg>;
+    fn configure_explanation(&mut self, matches: &ArgMatches);
+    fn explain(&self, input: &dyn Any) -> Option<Result<String, AocError>>;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError>;
+    fn part1(&self, input: &dyn Any) -> Result<Answer, AocError>;
+    fn part2(&self, input: &dyn Any) -> Result<Answer, AocError>;
+}
+
+impl<S: Solver> AnySolver for S {
+    fn arguments(&self) -> Vec<Arg> {
+        return Solver::arguments(self);
+    }
+
+    fn configure(&mut self, matches: &ArgMatches) {
+        Solver::configure(self, matches);
+    }
+
+    fn explain_arguments(&self) -> Vec<Arg> {
+        return Solver::explain_arguments(self);
+    }
+
+    fn configure_explanation(&mut self, matches: &ArgMatches) {
+        Solver::configure_explanation(self, matches);
+    }
+
+    fn explain(&self, input: &dyn Any) -> Option<Result<String, AocError>> {
+        return match downcast_input::<S>(input) {
+            Ok(input) => Solver::explain(self, input),
+            Err(error) => Some(Err(error)),
+        };
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        return Ok(Box::new(Solver::parse(self, input)?));
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        return Solver::part1(self, downcast_input::<S>(input)?);
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        return Solver::part2(self, downcast_input::<S>(input)?);
+    }
+}
+
+fn downcast_input<S: Solver>(input: &dyn Any) -> Result<&S::Input, AocError> {
+    return input
+        .downcast_ref::<S::Input>()
+        .ok_or_else(|| AocError::validation("the input was not parsed by this day"));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::{all, find};
+
+    #[test]
+    fn test_all_days_are_registered_in_order() {
+        let days = all();
+
+        assert_eq!(days.len(), 12);
+        for (index, day) in days.iter().enumerate() {
+            assert_eq!(day.number as usize, index + 1);
+            assert_eq!(day.name, format!("day{}", index + 1));
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("day7").unwrap().number, 7);
+        assert!(find("day42").is_none());
+    }
+
+    #[test]
+    fn test_input_of_other_day_is_rejected() {
+        let day6 = find("day6").unwrap();
+        let day9 = find("day9").unwrap();
+        let input = day9.parse("0 3 6 9 12 15").unwrap();
+
+        assert!(day6.part1(input.as_ref()).is_err());
+        assert_eq!(day9.part1(input.as_ref()).unwrap().to_string(), "18");
+    }
+}
@@ -0,0 +1,227 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::input::read_file;
+use crate::registry::Day;
+use crate::solver::Answer;
+
+pub const PARTS: [u32; 2] = [1, 2];
+
+pub struct PartRun {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Result<Answer, AocError>,
+    pub elapsed: Duration, // includes parsing the input
+}
+
+impl PartRun {
+    /// Parts that are not implemented yet are skipped rather than failed.
+    pub fn failed(&self) -> bool {
+        return !matches!(self.answer, Ok(_) | Err(AocError::NotImplemented));
+    }
+}
+
+/// Finds the input of a day part by the naming convention `dayN-challengeP.txt`.
+///
+/// Parts without an input of their own share the input of the first challenge.
+pub fn find_input(directory: &Path, day: &Day, part: u32) -> Option<PathBuf> {
+    let own_input = directory.join(format!("{}-challenge{part}.txt", day.name));
+    if own_input.is_file() {
+        return Some(own_input);
+    }
+
+    let shared_input = directory.join(format!("{}-challenge1.txt", day.name));
+    if shared_input.is_file() {
+        return Some(shared_input);
+    }
+
+    return None;
+}
+
+pub fn run_part(day: &Day, part: u32, file: &Path) -> PartRun {
+    let start = Instant::now();
+    let answer = solve_part(day, part, file);
+
+    return PartRun {
+        day: day.number,
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    };
+}
+
+fn solve_part(day: &Day, part: u32, file: &Path) -> Result<Answer, AocError> {
+    let file = file.to_string_lossy();
+    let text = read_file(&file)?;
+    let input = day.parse(&text).map_err(|e| e.in_file(&file))?;
+
+    let answer = match part {
+        1 => day.part1(input.as_ref()),
+        2 => day.part2(input.as_ref()),
+        _ => Err(AocError::validation(format!("there is no part {part}"))),
+    };
+
+    return answer.map_err(|e| e.in_file(&file));
+}
+
+/// Runs both parts of all given days, skipping days without an input in `directory`.
+pub fn run_all(days: &[&Day], directory: &Path) -> Vec<PartRun> {
+    let mut result = Vec::new();
+    for day in days {
+        if find_input(directory, day, 1).is_none() {
+            eprintln!(
+                "Skipping Day {}: no input found in {}",
+                day.number,
+                directory.display()
+            );
+            continue;
+        }
+
+        for part in PARTS {
+            if let Some(file) = find_input(directory, day, part) {
+                result.push(run_part(day, part, &file));
+            }
+        }
+    }
+
+    return result;
+}
+
+pub fn format_table(runs: &[PartRun]) -> String {
+    let header = ["Day", "Part", "Answer", "Time"];
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let answer = match &run.answer {
+                Ok(answer) => answer.to_string(),
+                Err(AocError::NotImplemented) => String::from("not implemented"),
+                Err(_) => String::from("failed"),
+            };
+
+            return [
+                run.day.to_string(),
+                run.part.to_string(),
+                answer,
+                format!("{:.2?}", run.elapsed),
+            ];
+        })
+        .collect::<Vec<[String; 4]>>();
+
+    let mut widths = header.map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut result = format_row(&header.map(String::from), &widths);
+    result.push_str(&format_row(&widths.map(|w| "-".repeat(w)), &widths));
+    for row in &rows {
+        result.push_str(&format_row(row, &widths));
+    }
+
+    return result;
+}
+
+fn format_row(cells: &[String; 4], widths: &[usize; 4]) -> String {
+    return format!(
+        "{:>w0$} | {:>w1$} | {:>w2$} | {:>w3$}\n",
+        cells[0],
+        cells[1],
+        cells[2],
+        cells[3],
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2],
+        w3 = widths[3]
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use crate::error::AocError;
+    use crate::registry::find;
+    use crate::runner::{find_input, format_table, run_all, PartRun};
+    use crate::solver::Answer;
+
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("aoc-runner-{name}"));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        return directory;
+    }
+
+    #[test]
+    fn test_find_input() {
+        let directory = test_directory("find-input");
+        fs::write(directory.join("day6-challenge1.txt"), "").unwrap();
+        fs::write(directory.join("day6-challenge2.txt"), "").unwrap();
+        fs::write(directory.join("day9-challenge1.txt"), "").unwrap();
+        let day6 = find("day6").unwrap();
+        let day7 = find("day7").unwrap();
+        let day9 = find("day9").unwrap();
+
+        assert_eq!(
+            find_input(&directory, &day6, 2),
+            Some(directory.join("day6-challenge2.txt"))
+        );
+        assert_eq!(
+            find_input(&directory, &day9, 2),
+            Some(directory.join("day9-challenge1.txt"))
+        );
+        assert_eq!(find_input(&directory, &day7, 1), None);
+    }
+
+    #[test]
+    fn test_run_all_continues_after_failures() {
+        let directory = test_directory("run-all");
+        fs::write(directory.join("day7-challenge1.txt"), "32T3K 7a5").unwrap();
+        fs::write(directory.join("day9-challenge1.txt"), "0 3 6 9 12 15").unwrap();
+        let day7 = find("day7").unwrap();
+        let day8 = find("day8").unwrap();
+        let day9 = find("day9").unwrap();
+
+        let runs = run_all(&[&day7, &day8, &day9], &directory);
+
+        assert_eq!(runs.len(), 4);
+        assert!(matches!(runs[0].answer, Err(AocError::Parse(_))));
+        assert!(runs[0].failed());
+        assert!(!runs[2].failed());
+        assert_eq!(runs[2].day, 9);
+        assert_eq!(runs[2].answer.as_ref().unwrap(), &Answer::Signed(18));
+        assert_eq!(runs[3].answer.as_ref().unwrap(), &Answer::Signed(-3));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let runs = vec![
+            PartRun {
+                day: 9,
+                part: 1,
+                answer: Ok(Answer::Unsigned(18)),
+                elapsed: Duration::from_millis(12),
+            },
+            PartRun {
+                day: 10,
+                part: 2,
+                answer: Err(AocError::NotImplemented),
+                elapsed: Duration::from_millis(3),
+            },
+        ];
+
+        assert_eq!(
+            format_table(&runs),
+            "\
+Day | Part |          Answer |    Time
+--- | ---- | --------------- | -------
+  9 |    1 |              18 | 12.00ms
+ 10 |    2 | not implemented |  3.00ms
+"
+        );
+    }
+}
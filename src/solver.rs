@@ -0,0 +1,88 @@
+use std::fmt::{Display, Formatter};
+
+use clap::{Arg, ArgMatches};
+
+use crate::error::AocError;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        return Answer::Unsigned(value as u128);
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        return Answer::Unsigned(value as u128);
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        return Answer::Unsigned(value);
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        return Answer::Signed(value);
+    }
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        return match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+        };
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Signed(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Solves both parts of a single day.
+///
+/// The puzzle input is parsed once via `parse` and then shared by `part1` and `part2`.
+/// Days with options of their own declare them in `arguments` and read them in `configure`,
+/// options that only affect `explain` go in `explain_arguments` and `configure_explanation`
+/// so that benchmarks can still accept the others.
+pub trait Solver {
+    type Input: 'static;
+
+    fn arguments(&self) -> Vec<Arg> {
+        return Vec::new();
+    }
+
+    fn configure(&mut self, _matches: &ArgMatches) {}
+
+    fn explain_arguments(&self) -> Vec<Arg> {
+        return Vec::new();
+    }
+
+    fn configure_explanation(&mut self, _matches: &ArgMatches) {}
+
+    /// Explains the input instead of solving it, if `configure_explanation` asked for an explanation.
+    fn explain(&self, _input: &Self::Input) -> Option<Result<String, AocError>> {
+        return None;
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn part2(&self, _input: &Self::Input) -> Result<Answer, AocError> {
+        return Err(AocError::NotImplemented);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::GenericError;
+use crate::error::AocError;
 
 pub fn split_and_clean<'a>(input: &'a str, separator: &str) -> Vec<&'a str> {
     let mut parts = input
@@ -10,16 +10,16 @@ pub fn split_and_clean<'a>(input: &'a str, separator: &str) -> Vec<&'a str> {
     return parts;
 }
 
-pub fn to_u128(input: &str) -> Result<u128, GenericError> {
+pub fn to_u128(input: &str) -> Result<u128, AocError> {
     let maybe_result = input.parse::<u128>();
     if maybe_result.is_err() {
-        return Err(GenericError::new("unable to convert string to u128"));
+        return Err(AocError::parse("unable to convert string to u128", input));
     }
 
     return Ok(maybe_result.unwrap());
 }
 
-pub fn all_to_u128(input: &[&str]) -> Result<Vec<u128>, GenericError> {
+pub fn all_to_u128(input: &[&str]) -> Result<Vec<u128>, AocError> {
     let mut result: Vec<u128> = Vec::new();
     for x in input {
         result.push(to_u128(x)?);
@@ -28,16 +28,16 @@ pub fn all_to_u128(input: &[&str]) -> Result<Vec<u128>, GenericError> {
     return Ok(result);
 }
 
-pub fn to_i128(input: &str) -> Result<i128, GenericError> {
+pub fn to_i128(input: &str) -> Result<i128, AocError> {
     let maybe_result = input.parse::<i128>();
     if maybe_result.is_err() {
-        return Err(GenericError::new("unable to convert string to i128"));
+        return Err(AocError::parse("unable to convert string to i128", input));
     }
 
     return Ok(maybe_result.unwrap());
 }
 
-pub fn all_to_i128(input: &[&str]) -> Result<Vec<i128>, GenericError> {
+pub fn all_to_i128(input: &[&str]) -> Result<Vec<i128>, AocError> {
     let mut result: Vec<i128> = Vec::new();
     for x in input {
         result.push(to_i128(x)?);
@@ -46,16 +46,16 @@ pub fn all_to_i128(input: &[&str]) -> Result<Vec<i128>, GenericError> {
     return Ok(result);
 }
 
-pub fn to_usize(input: &str) -> Result<usize, GenericError> {
+pub fn to_usize(input: &str) -> Result<usize, AocError> {
     let maybe_result = input.parse::<usize>();
     if maybe_result.is_err() {
-        return Err(GenericError::new("unable to convert string to usize"));
+        return Err(AocError::parse("unable to convert string to usize", input));
     }
 
     return Ok(maybe_result.unwrap());
 }
 
-pub fn all_to_usize(input: &[&str]) -> Result<Vec<usize>, GenericError> {
+pub fn all_to_usize(input: &[&str]) -> Result<Vec<usize>, AocError> {
     let mut result = Vec::new();
     for x in input {
         result.push(to_usize(x)?);
@@ -63,3 +63,10 @@ pub fn all_to_usize(input: &[&str]) -> Result<Vec<usize>, GenericError> {
 
     return Ok(result);
 }
+
+pub fn first_non_ascii(input: &str) -> Option<&str> {
+    return input
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii())
+        .map(|(index, c)| &input[index..index + c.len_utf8()]);
+}